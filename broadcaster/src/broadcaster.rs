@@ -1,66 +1,202 @@
 #![allow(dead_code)]
 
 use rand::Rng;
-use randomness_oracle_program::{id, instruction, state::RandomnessOracle};
+use randomness_oracle_program::{
+    id,
+    instruction::{self, RandomnessOracleUpdate},
+    state::{RandomnessOracle, RandomnessRequest},
+};
 use solana_client::{client_error::ClientError, rpc_client::RpcClient};
-use solana_program::pubkey::Pubkey;
+use solana_program::{instruction::Instruction, pubkey::Pubkey};
 use solana_sdk::{
     signature::{Keypair, Signature},
     signer::Signer,
     transaction::Transaction,
 };
+use std::{collections::HashMap, sync::Mutex};
 use tokio::task::JoinHandle;
 
+/// Number of failed fulfillments after which the request is skipped.
+pub const MAX_FULFILL_ATTEMPTS: u32 = 3;
+
 pub struct Broadcaster {
     pub rpc_url: String,
     pub authority: Keypair,
+    /// Failed fulfillments by randomness request.
+    pub failures: Mutex<HashMap<Pubkey, u32>>,
+    /// Failed updates by randomness oracle.
+    pub update_failures: Mutex<HashMap<Pubkey, u32>>,
 }
 
 impl Broadcaster {
     pub fn new(rpc_url: String, authority: Keypair) -> Self {
-        Self { rpc_url, authority }
+        Self {
+            rpc_url,
+            authority,
+            failures: Mutex::new(HashMap::new()),
+            update_failures: Mutex::new(HashMap::new()),
+        }
     }
 
     pub async fn broadcast(&self, accounts: Vec<(Pubkey, RandomnessOracle)>) -> Vec<Signature> {
         let mut signatures = vec![];
-        let mut rng = rand::thread_rng();
 
-        for (pubkey, _) in accounts {
+        let updates: Vec<RandomnessOracleUpdate> = {
+            let mut rng = rand::thread_rng();
+            accounts
+                .iter()
+                .map(|(pubkey, randomness_oracle)| RandomnessOracleUpdate {
+                    randomness_oracle: *pubkey,
+                    history: randomness_oracle.history_address(),
+                    value: rng.gen(),
+                })
+                .collect()
+        };
+
+        // Forget oracles no longer broadcast
+        self.update_failures.lock().unwrap().retain(|pubkey, _| {
+            updates
+                .iter()
+                .any(|update| update.randomness_oracle == *pubkey)
+        });
+
+        // Oracles are packed into as few transactions as possible
+        for instruction in
+            instruction::update_randomness_oracles(&id(), &self.authority.pubkey(), &updates)
+        {
+            let batch: Vec<&RandomnessOracleUpdate> = updates
+                .iter()
+                .filter(|update| {
+                    instruction
+                        .accounts
+                        .iter()
+                        .any(|account| account.pubkey == update.randomness_oracle)
+                })
+                .collect();
+
+            match self.send(instruction).await {
+                Ok(signature) => signatures.push(signature),
+                // Failing oracle shouldn't stop the rest of the batch from being updated
+                Err(e) => {
+                    eprintln!("error: {}", e);
+
+                    for update in batch {
+                        let instruction = instruction::update_randomness_oracles(
+                            &id(),
+                            &self.authority.pubkey(),
+                            std::slice::from_ref(update),
+                        )
+                        .remove(0);
+
+                        match self.send(instruction).await {
+                            Ok(signature) => signatures.push(signature),
+                            Err(e) => {
+                                eprintln!("error: {}", e);
+                                *self
+                                    .update_failures
+                                    .lock()
+                                    .unwrap()
+                                    .entry(update.randomness_oracle)
+                                    .or_insert(0) += 1;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        signatures
+    }
+
+    async fn send(&self, instruction: Instruction) -> Result<Signature, ClientError> {
+        let rpc_url = self.rpc_url.clone();
+        let authority = Keypair::from_bytes(&self.authority.to_bytes()[..]).unwrap();
+
+        let handle: JoinHandle<Result<Signature, ClientError>> = tokio::spawn(async move {
+            let rpc_client = RpcClient::new(rpc_url);
+
+            send_instruction(&rpc_client, instruction, &authority)
+        });
+
+        handle.await.unwrap()
+    }
+
+    pub async fn fulfill(&self, requests: Vec<(Pubkey, RandomnessRequest)>) -> Vec<Signature> {
+        let mut signatures = vec![];
+
+        // Forget requests no longer pending
+        self.failures
+            .lock()
+            .unwrap()
+            .retain(|pubkey, _| requests.iter().any(|(request, _)| request == pubkey));
+
+        for (pubkey, randomness_request) in requests {
+            // Requests failing repeatedly are left to be cancelled by the requester
+            if self.failures(&pubkey) >= MAX_FULFILL_ATTEMPTS {
+                continue;
+            }
+
             let rpc_url = self.rpc_url.clone();
             let authority = Keypair::from_bytes(&self.authority.to_bytes()[..]).unwrap();
-            let value: [u8; 32] = rng.gen();
 
             let handle: JoinHandle<Result<Signature, ClientError>> = tokio::spawn(async move {
                 let rpc_client = RpcClient::new(rpc_url);
 
-                update_randomness_oracle(&rpc_client, &pubkey, &authority, value)
+                fulfill_randomness(&rpc_client, &pubkey, &randomness_request, &authority)
             });
 
-            let signature = handle
-                .await
-                .unwrap()
-                .map_err(|e| eprintln!("error: {}", e.to_string()))
-                .unwrap();
-
-            signatures.push(signature);
+            match handle.await.unwrap() {
+                Ok(signature) => signatures.push(signature),
+                // Failing callback shouldn't stop other requests from being fulfilled
+                Err(e) => {
+                    eprintln!("error: {}", e.to_string());
+                    *self.failures.lock().unwrap().entry(pubkey).or_insert(0) += 1;
+                }
+            }
         }
 
         signatures
     }
+
+    fn failures(&self, randomness_request: &Pubkey) -> u32 {
+        self.failures
+            .lock()
+            .unwrap()
+            .get(randomness_request)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+fn send_instruction(
+    rpc_client: &RpcClient,
+    instruction: Instruction,
+    authority: &Keypair,
+) -> Result<Signature, ClientError> {
+    let mut tx = Transaction::new_with_payer(&[instruction], Some(&authority.pubkey()));
+
+    let (recent_blockhash, _) = rpc_client.get_recent_blockhash()?;
+
+    tx.try_sign(&[authority], recent_blockhash)?;
+
+    rpc_client.send_transaction(&tx)
 }
 
-fn update_randomness_oracle(
+fn fulfill_randomness(
     rpc_client: &RpcClient,
     pubkey: &Pubkey,
+    randomness_request: &RandomnessRequest,
     authority: &Keypair,
-    value: [u8; 32],
 ) -> Result<Signature, ClientError> {
     let mut tx = Transaction::new_with_payer(
-        &[instruction::update_randomness_oracle(
+        &[instruction::fulfill_randomness(
             &id(),
             pubkey,
+            &randomness_request.randomness_oracle,
             &authority.pubkey(),
-            value,
+            randomness_request.subscription().as_ref(),
+            randomness_request.callback_program().as_ref(),
+            randomness_request.callback_accounts(),
         )],
         Some(&authority.pubkey()),
     );
@@ -1,7 +1,10 @@
 #![allow(dead_code)]
 
-use crate::{broadcaster::*, utils::get_program_accounts};
-use randomness_oracle_program::state::{AccountType, RandomnessOracle};
+use crate::{
+    broadcaster::*,
+    utils::{get_pending_randomness_requests, get_registered_randomness_oracles},
+};
+use randomness_oracle_program::state::{RandomnessOracle, RandomnessRequest, UpdateMode};
 use solana_client::{pubsub_client::PubsubClient, rpc_client::RpcClient};
 use solana_program::{clock::Slot, program_pack::Pack, pubkey::Pubkey};
 use solana_sdk::signer::Signer;
@@ -43,28 +46,54 @@ impl Subscriber {
                                 continue;
                             }
 
-                            let accounts: Vec<(Pubkey, RandomnessOracle)> = get_program_accounts(
-                                &rpc_client,
-                                AccountType::RandomnessOracle,
-                                &broadcaster.authority.pubkey(),
-                            )?
-                            .into_iter()
-                            .filter_map(|(address, account)| {
-                                match RandomnessOracle::unpack_unchecked(&account.data) {
-                                    Ok(pool) => Some((address, pool)),
-                                    _ => None,
-                                }
-                            })
-                            .collect();
+                            let randomness_oracles: Vec<(Pubkey, RandomnessOracle)> =
+                                get_registered_randomness_oracles(
+                                    &rpc_client,
+                                    &broadcaster.authority.pubkey(),
+                                )?
+                                .into_iter()
+                                .filter_map(|(address, account)| {
+                                    RandomnessOracle::unpack_unchecked(&account.data)
+                                        .ok()
+                                        .map(|pool| (address, pool))
+                                })
+                                // Paused oracles reject both updates and fulfillments
+                                .filter(|(_, pool)| !pool.paused)
+                                .collect();
+
+                            let addresses: Vec<Pubkey> = randomness_oracles
+                                .iter()
+                                .map(|(address, _)| *address)
+                                .collect();
+                            let requests: Vec<(Pubkey, RandomnessRequest)> =
+                                get_pending_randomness_requests(&rpc_client, &addresses)?
+                                    .into_iter()
+                                    .filter_map(|(address, account)| {
+                                        RandomnessRequest::unpack(&account.data)
+                                            .ok()
+                                            .filter(|request| !request.fulfilled)
+                                            .map(|request| (address, request))
+                                    })
+                                    .collect();
+
+                            // Skip oracles the program would reject as updated too early,
+                            // the broadcaster only submits values directly
+                            let accounts: Vec<(Pubkey, RandomnessOracle)> = randomness_oracles
+                                .into_iter()
+                                .filter(|(_, pool)| pool.update_mode == UpdateMode::Direct)
+                                .filter(|(_, pool)| pool.can_update(current_root))
+                                .collect();
 
                             println!(
-                                "Latest stable block: {}, Pending block: {}, Total accounts: {}",
+                                "Latest stable block: {}, Pending block: {}, Total accounts: {}, Pending requests: {}",
                                 current_root,
                                 current_root - value,
                                 accounts.len(),
+                                requests.len(),
                             );
 
                             let _signatures = broadcaster.broadcast(accounts).await;
+                            let _signatures = broadcaster.fulfill(requests).await;
                             // println!("{:?}", signatures);
 
                             current_slot = Some(current_root);
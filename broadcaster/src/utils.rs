@@ -1,14 +1,13 @@
 #![allow(dead_code)]
 
-use randomness_oracle_program::{id, state::AccountType};
-use solana_account_decoder::UiAccountEncoding;
+use randomness_oracle_program::{
+    find_registry_address, find_request_queue_address, id,
+    state::{RandomnessOracleRegistry, RandomnessRequestQueue},
+};
 use solana_client::{
-    client_error::ClientError,
-    rpc_client::RpcClient,
-    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
-    rpc_filter::{Memcmp, MemcmpEncodedBytes, MemcmpEncoding, RpcFilterType},
+    client_error::ClientError, rpc_client::RpcClient, rpc_request::MAX_MULTIPLE_ACCOUNTS,
 };
-use solana_program::pubkey::Pubkey;
+use solana_program::{program_pack::Pack, pubkey::Pubkey};
 use solana_sdk::{account::Account, signature::Keypair};
 
 pub struct Config {
@@ -17,35 +16,66 @@ pub struct Config {
     pub authority: Keypair,
 }
 
-pub fn get_program_accounts(
+/// Get randomness oracles listed in the registry of the authority.
+pub fn get_registered_randomness_oracles(
+    rpc_client: &RpcClient,
+    authority: &Pubkey,
+) -> Result<Vec<(Pubkey, Account)>, ClientError> {
+    let (registry, _) = find_registry_address(&id(), authority);
+    let registry = match rpc_client
+        .get_account_with_commitment(&registry, rpc_client.commitment())?
+        .value
+    {
+        Some(account) => account,
+        // Registry is created along with the first randomness oracle
+        None => return Ok(vec![]),
+    };
+
+    let oracles = match RandomnessOracleRegistry::unpack(&registry.data) {
+        Ok(registry) => registry.oracles().to_vec(),
+        Err(_) => return Ok(vec![]),
+    };
+
+    Ok(oracles
+        .iter()
+        .zip(rpc_client.get_multiple_accounts(&oracles)?)
+        .filter_map(|(address, account)| account.map(|account| (*address, account)))
+        .collect())
+}
+
+/// Get pending randomness requests listed in the request queues of the randomness oracles.
+pub fn get_pending_randomness_requests(
     rpc_client: &RpcClient,
-    account_type: AccountType,
-    pubkey: &Pubkey,
+    randomness_oracles: &[Pubkey],
 ) -> Result<Vec<(Pubkey, Account)>, ClientError> {
-    rpc_client.get_program_accounts_with_config(
-        &id(),
-        RpcProgramAccountsConfig {
-            filters: Some(vec![
-                // Account type
-                RpcFilterType::Memcmp(Memcmp {
-                    offset: 0,
-                    bytes: MemcmpEncodedBytes::Binary(
-                        bs58::encode([account_type as u8]).into_string(),
-                    ),
-                    encoding: Some(MemcmpEncoding::Binary),
-                }),
-                // Account parent
-                RpcFilterType::Memcmp(Memcmp {
-                    offset: 1,
-                    bytes: MemcmpEncodedBytes::Binary(pubkey.to_string()),
-                    encoding: Some(MemcmpEncoding::Binary),
-                }),
-            ]),
-            account_config: RpcAccountInfoConfig {
-                encoding: Some(UiAccountEncoding::Base64Zstd),
-                ..RpcAccountInfoConfig::default()
-            },
-            ..RpcProgramAccountsConfig::default()
-        },
-    )
+    let queues: Vec<Pubkey> = randomness_oracles
+        .iter()
+        .map(|randomness_oracle| find_request_queue_address(&id(), randomness_oracle).0)
+        .collect();
+
+    // Request queue is created along with the first request
+    let mut requests = vec![];
+    for chunk in queues.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        for account in rpc_client
+            .get_multiple_accounts(chunk)?
+            .into_iter()
+            .flatten()
+        {
+            if let Ok(queue) = RandomnessRequestQueue::unpack(&account.data) {
+                requests.extend_from_slice(queue.requests());
+            }
+        }
+    }
+
+    let mut accounts = vec![];
+    for chunk in requests.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        accounts.extend(
+            chunk
+                .iter()
+                .zip(rpc_client.get_multiple_accounts(chunk)?)
+                .filter_map(|(address, account)| account.map(|account| (*address, account))),
+        );
+    }
+
+    Ok(accounts)
 }
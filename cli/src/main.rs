@@ -1,12 +1,22 @@
 use clap::{
     crate_description, crate_name, crate_version, value_t, App, AppSettings, Arg, SubCommand,
 };
-use randomness_oracle_program::{id, instruction, state::RandomnessOracle};
+use randomness_oracle_program::{
+    find_bond_vault_address, find_program_config_address, find_randomness_oracle_address,
+    find_registry_address, find_subscription_address, find_treasury_address, id, instruction,
+    is_valid_randomness_oracle_name,
+    state::{
+        RandomnessOracle, RandomnessOracleHistory, RandomnessOracleMetadata,
+        RandomnessOracleRegistry, RandomnessRequest, Subscription, UpdateMode, MIN_BOND_LAMPORTS,
+    },
+};
 
 use solana_clap_utils::{
     fee_payer::fee_payer_arg,
     input_parsers::{keypair_of, pubkey_of},
-    input_validators::{is_keypair, is_keypair_or_ask_keyword, is_pubkey, is_url_or_moniker},
+    input_validators::{
+        is_keypair, is_keypair_or_ask_keyword, is_parsable, is_pubkey, is_url_or_moniker,
+    },
     keypair::signer_from_path,
 };
 use solana_client::rpc_client::RpcClient;
@@ -37,6 +47,18 @@ macro_rules! unique_signers {
     };
 }
 
+/// Update mode names accepted by `--update-mode`.
+const UPDATE_MODES: &[&str] = &["direct", "commit-reveal", "mix", "signature"];
+
+fn parse_update_mode(name: &str) -> UpdateMode {
+    match name {
+        "commit-reveal" => UpdateMode::CommitReveal,
+        "mix" => UpdateMode::Mix,
+        "signature" => UpdateMode::Signature,
+        _ => UpdateMode::Direct,
+    }
+}
+
 fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(), Error> {
     let balance = config.rpc_client.get_balance(&config.fee_payer.pubkey())?;
     if balance < required_balance {
@@ -52,11 +74,28 @@ fn check_fee_payer_balance(config: &Config, required_balance: u64) -> Result<(),
     }
 }
 
-fn command_init_randomness_oracle(config: &Config, keypair: Option<Keypair>) -> CommandResult {
+fn command_init_randomness_oracle(
+    config: &Config,
+    keypair: Option<Keypair>,
+    min_update_interval_slots: u64,
+    metadata: RandomnessOracleMetadata,
+    bond: u64,
+    update_mode: UpdateMode,
+) -> CommandResult {
+    if !metadata.is_valid() {
+        return Err("Name or URI is too long".into());
+    }
+
     let keypair = keypair.unwrap_or_else(Keypair::new);
 
     println!("Creating account {}", keypair.pubkey());
     println!("Authority: {}", &config.owner.pubkey());
+    println!(
+        "Minimum update interval: {} slots",
+        min_update_interval_slots
+    );
+    println!("Bond: {} SOL", lamports_to_sol(bond));
+    println!("Update mode: {:?}", update_mode);
 
     let account_balance = config
         .rpc_client
@@ -74,7 +113,15 @@ fn command_init_randomness_oracle(config: &Config, keypair: Option<Keypair>) ->
                 &id(),
             ),
             // Initialize pool market account
-            instruction::init_randomness_oracle(&id(), &keypair.pubkey(), &config.owner.pubkey()),
+            instruction::init_randomness_oracle(
+                &id(),
+                &keypair.pubkey(),
+                &config.owner.pubkey(),
+                min_update_interval_slots,
+                metadata,
+                bond,
+                update_mode,
+            ),
         ],
         Some(&config.fee_payer.pubkey()),
     );
@@ -93,152 +140,1549 @@ fn command_init_randomness_oracle(config: &Config, keypair: Option<Keypair>) ->
     Ok(Some(tx))
 }
 
+fn command_create_randomness_oracle(
+    config: &Config,
+    min_update_interval_slots: u64,
+    metadata: RandomnessOracleMetadata,
+    bond: u64,
+    update_mode: UpdateMode,
+) -> CommandResult {
+    if !metadata.is_valid() || !is_valid_randomness_oracle_name(&metadata.name) {
+        return Err("Name or URI is too long".into());
+    }
+
+    let (randomness_oracle, _) =
+        find_randomness_oracle_address(&id(), &config.owner.pubkey(), &metadata.name);
+
+    println!("Creating account {}", randomness_oracle);
+    println!("Authority: {}", &config.owner.pubkey());
+    println!("Name: {}", metadata.name);
+    println!("Bond: {} SOL", lamports_to_sol(bond));
+    println!("Update mode: {:?}", update_mode);
+
+    let account_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(RandomnessOracle::LEN)?;
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::create_randomness_oracle(
+            &id(),
+            &config.owner.pubkey(),
+            metadata,
+            min_update_interval_slots,
+            bond,
+            update_mode,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    // Account rent and bond are paid by authority
+    let authority_balance = config.rpc_client.get_balance(&config.owner.pubkey())?;
+    if authority_balance < account_balance + bond {
+        return Err(format!(
+            "Authority, {}, has insufficient balance: {} required, {} available",
+            config.owner.pubkey(),
+            lamports_to_sol(account_balance + bond),
+            lamports_to_sol(authority_balance)
+        )
+        .into());
+    }
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
 fn command_randomness_oracle_info(config: &Config, pubkey: &Pubkey) -> CommandResult {
     let account = config.rpc_client.get_account(pubkey)?;
     let randomness_oracle = RandomnessOracle::unpack(&account.data)?;
+    let metadata = randomness_oracle.metadata();
+
+    println!("Name: {}", metadata.name);
+    println!("URI: {}", metadata.uri);
+    println!("Update interval: {} slots", metadata.update_interval_slots);
+    println!(
+        "Minimum update interval: {} slots",
+        randomness_oracle.min_update_interval_slots
+    );
+    println!("Authority: {}", randomness_oracle.authority);
+    println!(
+        "Value: {}",
+        randomness_oracle
+            .value
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<String>()
+    );
+    println!("Slot: {}", randomness_oracle.slot);
+    println!("Round: {}", randomness_oracle.round);
+    println!("Update mode: {:?}", randomness_oracle.update_mode);
+    println!("Paused: {}", randomness_oracle.paused);
+    println!("Approved: {}", randomness_oracle.approved);
+    println!(
+        "Request fee: {} SOL",
+        lamports_to_sol(randomness_oracle.request_fee)
+    );
 
-    println!("{:#?}", randomness_oracle);
+    let (treasury, _) = find_treasury_address(&id(), pubkey);
+    println!(
+        "Treasury: {} ({} SOL)",
+        treasury,
+        lamports_to_sol(config.rpc_client.get_balance(&treasury)?)
+    );
+
+    let (bond_vault, _) = find_bond_vault_address(&id(), pubkey);
+    println!(
+        "Bond vault: {} ({} SOL)",
+        bond_vault,
+        lamports_to_sol(config.rpc_client.get_balance(&bond_vault)?)
+    );
+
+    if config.verbose {
+        println!("{:#?}", randomness_oracle);
+    }
 
     Ok(None)
 }
 
-fn main() {
-    let matches = App::new(crate_name!())
-        .about(crate_description!())
-        .version(crate_version!())
-        .setting(AppSettings::SubcommandRequiredElseHelp)
-        .arg({
-            let arg = Arg::with_name("config_file")
-                .short("C")
-                .long("config")
-                .value_name("PATH")
-                .takes_value(true)
-                .global(true)
-                .help("Configuration file to use");
-            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
-                arg.default_value(config_file)
-            } else {
-                arg
-            }
-        })
-        .arg(
-            Arg::with_name("verbose")
-                .short("v")
-                .long("verbose")
-                .takes_value(false)
-                .global(true)
-                .help("Show additional information"),
-        )
-        .arg(
-            Arg::with_name("json_rpc_url")
-                .short("u")
-                .long("url")
-                .value_name("URL_OR_MONIKER")
-                .takes_value(true)
-                .global(true)
-                .validator(is_url_or_moniker)
-                .help(
-                    "URL for Solana's JSON RPC or moniker (or their first letter): \
-                       [mainnet-beta, testnet, devnet, localhost] \
-                    Default from the configuration file.",
-                ),
-        )
-        .arg(
-            Arg::with_name("owner")
-                .long("owner")
-                .value_name("KEYPAIR")
-                .validator(is_keypair)
-                .takes_value(true)
-                .global(true)
-                .help(
-                    "Specify the token owner account. \
-                     This may be a keypair file, the ASK keyword. \
-                     Defaults to the client keypair.",
-                ),
-        )
-        .arg(fee_payer_arg().global(true))
-        .subcommand(
-            SubCommand::with_name("init")
-                .about("Init a new randomness oracle account")
-                .arg(
-                    Arg::with_name("keypair")
-                        .long("keypair")
-                        .validator(is_keypair_or_ask_keyword)
-                        .value_name("PATH")
-                        .takes_value(true)
-                        .help("Account keypair [default: new keypair]"),
-                ),
-        )
-        .subcommand(
-            SubCommand::with_name("info")
-                .about("Print out randomness oracle information")
-                .arg(
-                    Arg::with_name("pubkey")
-                        .validator(is_pubkey)
-                        .value_name("ADDRESS")
-                        .takes_value(true)
-                        .required(true)
-                        .index(1)
-                        .help("Randomness oracle pubkey"),
-                ),
-        )
-        .get_matches();
+fn command_list_randomness_oracles(config: &Config, authority: Option<Pubkey>) -> CommandResult {
+    let authority = authority.unwrap_or_else(|| config.owner.pubkey());
+    let (registry, _) = find_registry_address(&id(), &authority);
 
-    let mut wallet_manager = None;
-    let config = {
-        let cli_config = if let Some(config_file) = matches.value_of("config_file") {
-            solana_cli_config::Config::load(config_file).unwrap_or_default()
-        } else {
-            solana_cli_config::Config::default()
-        };
+    println!("Authority: {}", authority);
+    println!("Registry: {}", registry);
 
-        let json_rpc_url = value_t!(matches, "json_rpc_url", String)
-            .unwrap_or_else(|_| cli_config.json_rpc_url.clone());
+    let account = config.rpc_client.get_account(&registry)?;
+    let registry = RandomnessOracleRegistry::unpack(&account.data)?;
 
-        let owner = signer_from_path(
-            &matches,
-            matches
-                .value_of("owner")
-                .unwrap_or(&cli_config.keypair_path),
-            "owner",
-            &mut wallet_manager,
-        )
-        .unwrap_or_else(|e| {
-            eprintln!("error: {}", e);
-            exit(1);
-        });
+    for (randomness_oracle, account) in registry.oracles().iter().zip(
+        config
+            .rpc_client
+            .get_multiple_accounts(registry.oracles())?,
+    ) {
+        let name = account
+            .and_then(|account| RandomnessOracle::unpack(&account.data).ok())
+            .map(|randomness_oracle| randomness_oracle.metadata().name)
+            .unwrap_or_default();
+        println!("{} {}", randomness_oracle, name);
+    }
 
-        let fee_payer = signer_from_path(
-            &matches,
-            &cli_config.keypair_path,
-            "fee_payer",
-            &mut wallet_manager,
-        )
-        .unwrap_or_else(|e| {
-            eprintln!("error: {}", e);
-            exit(1);
-        });
+    Ok(None)
+}
 
-        let verbose = matches.is_present("verbose");
+fn command_update_metadata(
+    config: &Config,
+    pubkey: &Pubkey,
+    name: Option<String>,
+    uri: Option<String>,
+    update_interval_slots: Option<u64>,
+) -> CommandResult {
+    let account = config.rpc_client.get_account(pubkey)?;
+    let randomness_oracle = RandomnessOracle::unpack(&account.data)?;
 
-        Config {
-            rpc_client: RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed()),
-            verbose,
-            owner,
-            fee_payer,
-        }
+    // Values which are not provided are kept
+    let current = randomness_oracle.metadata();
+    let metadata = RandomnessOracleMetadata {
+        name: name.unwrap_or(current.name),
+        uri: uri.unwrap_or(current.uri),
+        update_interval_slots: update_interval_slots.unwrap_or(current.update_interval_slots),
     };
+    if !metadata.is_valid() {
+        return Err("Name or URI is too long".into());
+    }
 
-    solana_logger::setup_with_default("solana=info");
+    println!("Randomness oracle: {}", pubkey);
+    println!("Name: {}", metadata.name);
+    println!("URI: {}", metadata.uri);
+    println!("Update interval: {} slots", metadata.update_interval_slots);
 
-    let _ = match matches.subcommand() {
-        ("init", Some(arg_matches)) => {
-            let keypair = keypair_of(arg_matches, "keypair");
-            command_init_randomness_oracle(&config, keypair)
-        }
-        ("info", Some(arg_matches)) => {
-            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
-            command_randomness_oracle_info(&config, &pubkey)
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::update_metadata(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+            metadata,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_init_randomness_oracle_history(
+    config: &Config,
+    pubkey: &Pubkey,
+    keypair: Option<Keypair>,
+) -> CommandResult {
+    let keypair = keypair.unwrap_or_else(Keypair::new);
+
+    println!("Creating account {}", keypair.pubkey());
+    println!("Randomness oracle: {}", pubkey);
+
+    let account_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(RandomnessOracleHistory::LEN)?;
+    let total_rent_free_balances = account_balance;
+
+    let mut tx = Transaction::new_with_payer(
+        &[
+            // History account
+            system_instruction::create_account(
+                &config.fee_payer.pubkey(),
+                &keypair.pubkey(),
+                account_balance,
+                RandomnessOracleHistory::LEN as u64,
+                &id(),
+            ),
+            // Initialize history account
+            instruction::init_randomness_oracle_history(
+                &id(),
+                &keypair.pubkey(),
+                pubkey,
+                &config.owner.pubkey(),
+            ),
+        ],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(
+        config,
+        total_rent_free_balances + fee_calculator.calculate_fee(tx.message()),
+    )?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref(), &keypair];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_set_min_update_interval(
+    config: &Config,
+    pubkey: &Pubkey,
+    min_update_interval_slots: u64,
+) -> CommandResult {
+    println!("Randomness oracle: {}", pubkey);
+    println!(
+        "Minimum update interval: {} slots",
+        min_update_interval_slots
+    );
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::set_min_update_interval(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+            min_update_interval_slots,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_set_request_fee(config: &Config, pubkey: &Pubkey, request_fee: u64) -> CommandResult {
+    println!("Randomness oracle: {}", pubkey);
+    println!("Request fee: {} SOL", lamports_to_sol(request_fee));
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::set_request_fee(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+            request_fee,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_withdraw_fees(
+    config: &Config,
+    pubkey: &Pubkey,
+    destination: Option<Pubkey>,
+) -> CommandResult {
+    let destination = destination.unwrap_or_else(|| config.owner.pubkey());
+    let (treasury, _) = find_treasury_address(&id(), pubkey);
+
+    println!("Randomness oracle: {}", pubkey);
+    println!(
+        "Withdrawing {} SOL",
+        lamports_to_sol(config.rpc_client.get_balance(&treasury)?)
+    );
+    println!("Destination: {}", destination);
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::withdraw_fees(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+            &destination,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_create_subscription(config: &Config, pubkey: &Pubkey) -> CommandResult {
+    let (subscription, _) = find_subscription_address(&id(), pubkey, &config.owner.pubkey());
+
+    println!("Creating account {}", subscription);
+    println!("Randomness oracle: {}", pubkey);
+    println!("Owner: {}", config.owner.pubkey());
+
+    let total_rent_free_balances = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(Subscription::LEN)?;
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::create_subscription(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    // Owner pays for the subscription account
+    let owner_balance = config.rpc_client.get_balance(&config.owner.pubkey())?;
+    if owner_balance < total_rent_free_balances {
+        return Err(format!(
+            "Owner, {}, has insufficient balance: {} required, {} available",
+            config.owner.pubkey(),
+            lamports_to_sol(total_rent_free_balances),
+            lamports_to_sol(owner_balance)
+        )
+        .into());
+    }
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_fund_subscription(config: &Config, pubkey: &Pubkey, amount: u64) -> CommandResult {
+    println!("Subscription: {}", pubkey);
+    println!("Amount: {} SOL", lamports_to_sol(amount));
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::fund_subscription(
+            &id(),
+            pubkey,
+            &config.fee_payer.pubkey(),
+            amount,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, amount + fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_cancel_subscription(
+    config: &Config,
+    pubkey: &Pubkey,
+    destination: Option<Pubkey>,
+) -> CommandResult {
+    let destination = destination.unwrap_or_else(|| config.owner.pubkey());
+
+    println!("Closing account {}", pubkey);
+    println!("Destination: {}", destination);
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::cancel_subscription(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+            &destination,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_slash_operator(config: &Config, pubkey: &Pubkey) -> CommandResult {
+    println!("Slashing randomness oracle {}", pubkey);
+    println!("Caller: {}", config.owner.pubkey());
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::slash_operator(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_pause_randomness_oracle(config: &Config, pubkey: &Pubkey) -> CommandResult {
+    println!("Pausing randomness oracle {}", pubkey);
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::pause_randomness_oracle(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_resume_randomness_oracle(config: &Config, pubkey: &Pubkey) -> CommandResult {
+    println!("Resuming randomness oracle {}", pubkey);
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::resume_randomness_oracle(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_init_program_config(config: &Config, admin: &Pubkey) -> CommandResult {
+    let (program_config, _) = find_program_config_address(&id());
+
+    println!("Creating program config {}", program_config);
+    println!("Upgrade authority: {}", config.owner.pubkey());
+    println!("Admin: {}", admin);
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::init_program_config(
+            &id(),
+            &config.owner.pubkey(),
+            admin,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_approve_oracle(config: &Config, pubkey: &Pubkey) -> CommandResult {
+    println!("Approving randomness oracle {}", pubkey);
+    println!("Admin: {}", config.owner.pubkey());
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::approve_oracle(
+            &id(),
+            &config.owner.pubkey(),
+            pubkey,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_revoke_oracle(config: &Config, pubkey: &Pubkey) -> CommandResult {
+    println!("Revoking randomness oracle {}", pubkey);
+    println!("Admin: {}", config.owner.pubkey());
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::revoke_oracle(
+            &id(),
+            &config.owner.pubkey(),
+            pubkey,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_cancel_randomness_request(
+    config: &Config,
+    pubkey: &Pubkey,
+    destination: Option<Pubkey>,
+) -> CommandResult {
+    let account = config.rpc_client.get_account(pubkey)?;
+    let randomness_request = RandomnessRequest::unpack(&account.data)?;
+
+    let destination = destination.unwrap_or(randomness_request.requester);
+
+    println!("Cancelling randomness request {}", pubkey);
+    println!("Destination: {}", destination);
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::cancel_randomness_request(
+            &id(),
+            pubkey,
+            &randomness_request.randomness_oracle,
+            &config.owner.pubkey(),
+            &destination,
+            randomness_request.subscription().as_ref(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_propose_admin(config: &Config, new_admin: &Pubkey) -> CommandResult {
+    println!("Admin: {}", config.owner.pubkey());
+    println!("Proposed admin: {}", new_admin);
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::propose_admin(
+            &id(),
+            &config.owner.pubkey(),
+            new_admin,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_accept_admin(config: &Config) -> CommandResult {
+    println!("New admin: {}", config.owner.pubkey());
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::accept_admin(&id(), &config.owner.pubkey())],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_propose_authority(
+    config: &Config,
+    pubkey: &Pubkey,
+    new_authority: &Pubkey,
+) -> CommandResult {
+    println!("Randomness oracle: {}", pubkey);
+    println!("Proposed authority: {}", new_authority);
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::propose_authority(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+            new_authority,
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_accept_authority(config: &Config, pubkey: &Pubkey) -> CommandResult {
+    let account = config.rpc_client.get_account(pubkey)?;
+    let randomness_oracle = RandomnessOracle::unpack(&account.data)?;
+
+    println!("Randomness oracle: {}", pubkey);
+    println!("Authority: {}", randomness_oracle.authority);
+    println!("New authority: {}", config.owner.pubkey());
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::accept_authority(
+            &id(),
+            pubkey,
+            &randomness_oracle.authority,
+            &config.owner.pubkey(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_migrate_randomness_oracle(
+    config: &Config,
+    pubkey: &Pubkey,
+    keypair: Option<Keypair>,
+) -> CommandResult {
+    let keypair = keypair.unwrap_or_else(Keypair::new);
+
+    println!("Migrating account {}", pubkey);
+    println!("Creating account {}", keypair.pubkey());
+
+    let account_balance = config
+        .rpc_client
+        .get_minimum_balance_for_rent_exemption(RandomnessOracle::LEN)?;
+    let total_rent_free_balances = account_balance;
+
+    let mut tx = Transaction::new_with_payer(
+        &[
+            // New randomness oracle account
+            system_instruction::create_account(
+                &config.fee_payer.pubkey(),
+                &keypair.pubkey(),
+                account_balance,
+                RandomnessOracle::LEN as u64,
+                &id(),
+            ),
+            // Migrate randomness oracle into the new account
+            instruction::migrate_randomness_oracle(
+                &id(),
+                pubkey,
+                &keypair.pubkey(),
+                &config.owner.pubkey(),
+            ),
+        ],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(
+        config,
+        total_rent_free_balances + fee_calculator.calculate_fee(tx.message()),
+    )?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref(), &keypair];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn command_close_randomness_oracle(
+    config: &Config,
+    pubkey: &Pubkey,
+    destination: Option<Pubkey>,
+) -> CommandResult {
+    let destination = destination.unwrap_or_else(|| config.owner.pubkey());
+    let account = config.rpc_client.get_account(pubkey)?;
+    let history = RandomnessOracle::unpack(&account.data)?.history_address();
+
+    println!("Closing account {}", pubkey);
+    if let Some(history) = history {
+        println!("Closing history {}", history);
+    }
+    println!("Destination: {}", destination);
+
+    let mut tx = Transaction::new_with_payer(
+        &[instruction::close_randomness_oracle(
+            &id(),
+            pubkey,
+            &config.owner.pubkey(),
+            &destination,
+            history.as_ref(),
+        )],
+        Some(&config.fee_payer.pubkey()),
+    );
+
+    let (recent_blockhash, fee_calculator) = config.rpc_client.get_recent_blockhash()?;
+    check_fee_payer_balance(config, fee_calculator.calculate_fee(tx.message()))?;
+
+    let mut signers = vec![config.fee_payer.as_ref(), config.owner.as_ref()];
+
+    unique_signers!(signers);
+    tx.sign(&signers, recent_blockhash);
+
+    Ok(Some(tx))
+}
+
+fn main() {
+    let matches = App::new(crate_name!())
+        .about(crate_description!())
+        .version(crate_version!())
+        .setting(AppSettings::SubcommandRequiredElseHelp)
+        .arg({
+            let arg = Arg::with_name("config_file")
+                .short("C")
+                .long("config")
+                .value_name("PATH")
+                .takes_value(true)
+                .global(true)
+                .help("Configuration file to use");
+            if let Some(ref config_file) = *solana_cli_config::CONFIG_FILE {
+                arg.default_value(config_file)
+            } else {
+                arg
+            }
+        })
+        .arg(
+            Arg::with_name("verbose")
+                .short("v")
+                .long("verbose")
+                .takes_value(false)
+                .global(true)
+                .help("Show additional information"),
+        )
+        .arg(
+            Arg::with_name("json_rpc_url")
+                .short("u")
+                .long("url")
+                .value_name("URL_OR_MONIKER")
+                .takes_value(true)
+                .global(true)
+                .validator(is_url_or_moniker)
+                .help(
+                    "URL for Solana's JSON RPC or moniker (or their first letter): \
+                       [mainnet-beta, testnet, devnet, localhost] \
+                    Default from the configuration file.",
+                ),
+        )
+        .arg(
+            Arg::with_name("owner")
+                .long("owner")
+                .value_name("KEYPAIR")
+                .validator(is_keypair)
+                .takes_value(true)
+                .global(true)
+                .help(
+                    "Specify the token owner account. \
+                     This may be a keypair file, the ASK keyword. \
+                     Defaults to the client keypair.",
+                ),
+        )
+        .arg(fee_payer_arg().global(true))
+        .subcommand(
+            SubCommand::with_name("init")
+                .about("Init a new randomness oracle account")
+                .arg(
+                    Arg::with_name("keypair")
+                        .long("keypair")
+                        .validator(is_keypair_or_ask_keyword)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("Account keypair [default: new keypair]"),
+                )
+                .arg(
+                    Arg::with_name("name")
+                        .long("name")
+                        .value_name("NAME")
+                        .takes_value(true)
+                        .help("Randomness oracle name, up to 32 bytes"),
+                )
+                .arg(
+                    Arg::with_name("uri")
+                        .long("uri")
+                        .value_name("URI")
+                        .takes_value(true)
+                        .help("URI of the randomness oracle description, up to 128 bytes"),
+                )
+                .arg(
+                    Arg::with_name("update_interval")
+                        .long("update-interval")
+                        .validator(is_parsable::<u64>)
+                        .value_name("SLOTS")
+                        .takes_value(true)
+                        .help("Declared target number of slots between updates"),
+                )
+                .arg(
+                    Arg::with_name("min_update_interval")
+                        .long("min-update-interval")
+                        .validator(is_parsable::<u64>)
+                        .value_name("SLOTS")
+                        .takes_value(true)
+                        .help("Minimum number of slots between updates [default: 0]"),
+                )
+                .arg(
+                    Arg::with_name("bond")
+                        .long("bond")
+                        .validator(is_parsable::<u64>)
+                        .value_name("LAMPORTS")
+                        .takes_value(true)
+                        .help("Lamports locked in the bond vault until close, at least 10000000 [default: 10000000]"),
+                )
+                .arg(
+                    Arg::with_name("update_mode")
+                        .long("update-mode")
+                        .possible_values(UPDATE_MODES)
+                        .value_name("MODE")
+                        .takes_value(true)
+                        .help("The only way the value can be updated, fixed at init [default: direct]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("create")
+                .about("Create a new randomness oracle at the address derived from owner and name")
+                .arg(
+                    Arg::with_name("name")
+                        .value_name("NAME")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle name, up to 32 bytes"),
+                )
+                .arg(
+                    Arg::with_name("uri")
+                        .long("uri")
+                        .value_name("URI")
+                        .takes_value(true)
+                        .help("URI of the randomness oracle description, up to 128 bytes"),
+                )
+                .arg(
+                    Arg::with_name("update_interval")
+                        .long("update-interval")
+                        .validator(is_parsable::<u64>)
+                        .value_name("SLOTS")
+                        .takes_value(true)
+                        .help("Declared target number of slots between updates"),
+                )
+                .arg(
+                    Arg::with_name("min_update_interval")
+                        .long("min-update-interval")
+                        .validator(is_parsable::<u64>)
+                        .value_name("SLOTS")
+                        .takes_value(true)
+                        .help("Minimum number of slots between updates [default: 0]"),
+                )
+                .arg(
+                    Arg::with_name("bond")
+                        .long("bond")
+                        .validator(is_parsable::<u64>)
+                        .value_name("LAMPORTS")
+                        .takes_value(true)
+                        .help("Lamports locked in the bond vault until close, at least 10000000 [default: 10000000]"),
+                )
+                .arg(
+                    Arg::with_name("update_mode")
+                        .long("update-mode")
+                        .possible_values(UPDATE_MODES)
+                        .value_name("MODE")
+                        .takes_value(true)
+                        .help("The only way the value can be updated, fixed at init [default: direct]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("info")
+                .about("Print out randomness oracle information")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("list")
+                .about("List randomness oracles registered for the authority")
+                .arg(
+                    Arg::with_name("authority")
+                        .long("authority")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .help("Randomness oracle authority [default: owner]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("update-metadata")
+                .about("Update randomness oracle name, URI and declared update interval")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                )
+                .arg(
+                    Arg::with_name("name")
+                        .long("name")
+                        .value_name("NAME")
+                        .takes_value(true)
+                        .help("Randomness oracle name, up to 32 bytes"),
+                )
+                .arg(
+                    Arg::with_name("uri")
+                        .long("uri")
+                        .value_name("URI")
+                        .takes_value(true)
+                        .help("URI of the randomness oracle description, up to 128 bytes"),
+                )
+                .arg(
+                    Arg::with_name("update_interval")
+                        .long("update-interval")
+                        .validator(is_parsable::<u64>)
+                        .value_name("SLOTS")
+                        .takes_value(true)
+                        .help("Declared target number of slots between updates"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("init-history")
+                .about("Init a history account keeping the latest randomness oracle values")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                )
+                .arg(
+                    Arg::with_name("keypair")
+                        .long("keypair")
+                        .validator(is_keypair_or_ask_keyword)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("History account keypair [default: new keypair]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-min-update-interval")
+                .about("Set the minimum number of slots between randomness oracle updates")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                )
+                .arg(
+                    Arg::with_name("min_update_interval")
+                        .validator(is_parsable::<u64>)
+                        .value_name("SLOTS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(2)
+                        .help("Minimum number of slots between updates"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("set-request-fee")
+                .about("Set the fee paid for each randomness request")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                )
+                .arg(
+                    Arg::with_name("request_fee")
+                        .validator(is_parsable::<u64>)
+                        .value_name("LAMPORTS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(2)
+                        .help("Lamports paid for each randomness request"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("withdraw-fees")
+                .about("Withdraw fees collected by the randomness oracle treasury")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .help("Account to receive lamports [default: owner]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("create-subscription")
+                .about("Create a subscription of the owner paying randomness oracle request fees")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("fund-subscription")
+                .about("Deposit lamports to the subscription balance")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Subscription pubkey"),
+                )
+                .arg(
+                    Arg::with_name("amount")
+                        .validator(is_parsable::<u64>)
+                        .value_name("LAMPORTS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(2)
+                        .help("Lamports to deposit"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("cancel-subscription")
+                .about("Cancel subscription and reclaim its lamports")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Subscription pubkey"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .help("Account to receive lamports [default: owner]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("slash")
+                .about("Slash the bond of a randomness oracle whose commitment was not revealed in time")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("pause")
+                .about("Pause randomness oracle updates and reads")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("resume")
+                .about("Resume paused randomness oracle")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("init-config")
+                .about("Init program config, signed by the program upgrade authority as owner")
+                .arg(
+                    Arg::with_name("admin")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Admin approving randomness oracles"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("approve")
+                .about("Approve randomness oracle as program config admin")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("revoke")
+                .about("Revoke randomness oracle approval as program config admin")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("cancel-request")
+                .about("Cancel expired randomness request and return its lamports to the requester")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness request pubkey"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .help("Account to receive lamports, only the requester can set it [default: requester]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("propose-admin")
+                .about("Propose a new program config admin as the admin")
+                .arg(
+                    Arg::with_name("new_admin")
+                        .validator(is_pubkey)
+                        .value_name("NEW_ADMIN")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Proposed admin pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("accept-admin")
+                .about("Accept program config admin role by the owner"),
+        )
+        .subcommand(
+            SubCommand::with_name("propose-authority")
+                .about("Propose a new randomness oracle authority")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                )
+                .arg(
+                    Arg::with_name("new_authority")
+                        .validator(is_pubkey)
+                        .value_name("NEW_AUTHORITY")
+                        .takes_value(true)
+                        .required(true)
+                        .index(2)
+                        .help("Proposed authority pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("accept-authority")
+                .about("Accept randomness oracle authority by the owner")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("migrate")
+                .about("Migrate version 1 randomness oracle into a new account, the oracle address changes")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                )
+                .arg(
+                    Arg::with_name("keypair")
+                        .long("keypair")
+                        .validator(is_keypair_or_ask_keyword)
+                        .value_name("PATH")
+                        .takes_value(true)
+                        .help("New account keypair [default: new keypair]"),
+                ),
+        )
+        .subcommand(
+            SubCommand::with_name("close")
+                .about("Close randomness oracle and reclaim its rent")
+                .arg(
+                    Arg::with_name("pubkey")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .required(true)
+                        .index(1)
+                        .help("Randomness oracle pubkey"),
+                )
+                .arg(
+                    Arg::with_name("destination")
+                        .long("destination")
+                        .validator(is_pubkey)
+                        .value_name("ADDRESS")
+                        .takes_value(true)
+                        .help("Account to receive lamports [default: owner]"),
+                ),
+        )
+        .get_matches();
+
+    let mut wallet_manager = None;
+    let config = {
+        let cli_config = if let Some(config_file) = matches.value_of("config_file") {
+            solana_cli_config::Config::load(config_file).unwrap_or_default()
+        } else {
+            solana_cli_config::Config::default()
+        };
+
+        let json_rpc_url = value_t!(matches, "json_rpc_url", String)
+            .unwrap_or_else(|_| cli_config.json_rpc_url.clone());
+
+        let owner = signer_from_path(
+            &matches,
+            matches
+                .value_of("owner")
+                .unwrap_or(&cli_config.keypair_path),
+            "owner",
+            &mut wallet_manager,
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(1);
+        });
+
+        let fee_payer = signer_from_path(
+            &matches,
+            &cli_config.keypair_path,
+            "fee_payer",
+            &mut wallet_manager,
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("error: {}", e);
+            exit(1);
+        });
+
+        let verbose = matches.is_present("verbose");
+
+        Config {
+            rpc_client: RpcClient::new_with_commitment(json_rpc_url, CommitmentConfig::confirmed()),
+            verbose,
+            owner,
+            fee_payer,
+        }
+    };
+
+    solana_logger::setup_with_default("solana=info");
+
+    let _ = match matches.subcommand() {
+        ("init", Some(arg_matches)) => {
+            let keypair = keypair_of(arg_matches, "keypair");
+            let min_update_interval_slots =
+                value_t!(arg_matches, "min_update_interval", u64).unwrap_or(0);
+            let metadata = RandomnessOracleMetadata {
+                name: value_t!(arg_matches, "name", String).unwrap_or_default(),
+                uri: value_t!(arg_matches, "uri", String).unwrap_or_default(),
+                update_interval_slots: value_t!(arg_matches, "update_interval", u64).unwrap_or(0),
+            };
+            let bond = value_t!(arg_matches, "bond", u64).unwrap_or(MIN_BOND_LAMPORTS);
+            let update_mode = parse_update_mode(arg_matches.value_of("update_mode").unwrap_or(""));
+            command_init_randomness_oracle(
+                &config,
+                keypair,
+                min_update_interval_slots,
+                metadata,
+                bond,
+                update_mode,
+            )
+        }
+        ("create", Some(arg_matches)) => {
+            let min_update_interval_slots =
+                value_t!(arg_matches, "min_update_interval", u64).unwrap_or(0);
+            let metadata = RandomnessOracleMetadata {
+                name: value_t!(arg_matches, "name", String).unwrap(),
+                uri: value_t!(arg_matches, "uri", String).unwrap_or_default(),
+                update_interval_slots: value_t!(arg_matches, "update_interval", u64).unwrap_or(0),
+            };
+            let bond = value_t!(arg_matches, "bond", u64).unwrap_or(MIN_BOND_LAMPORTS);
+            let update_mode = parse_update_mode(arg_matches.value_of("update_mode").unwrap_or(""));
+            command_create_randomness_oracle(
+                &config,
+                min_update_interval_slots,
+                metadata,
+                bond,
+                update_mode,
+            )
+        }
+        ("info", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            command_randomness_oracle_info(&config, &pubkey)
+        }
+        ("list", Some(arg_matches)) => {
+            let authority = pubkey_of(arg_matches, "authority");
+            command_list_randomness_oracles(&config, authority)
+        }
+        ("update-metadata", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let name = value_t!(arg_matches, "name", String).ok();
+            let uri = value_t!(arg_matches, "uri", String).ok();
+            let update_interval_slots = value_t!(arg_matches, "update_interval", u64).ok();
+            command_update_metadata(&config, &pubkey, name, uri, update_interval_slots)
+        }
+        ("init-history", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let keypair = keypair_of(arg_matches, "keypair");
+            command_init_randomness_oracle_history(&config, &pubkey, keypair)
+        }
+        ("set-min-update-interval", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let min_update_interval_slots =
+                value_t!(arg_matches, "min_update_interval", u64).unwrap();
+            command_set_min_update_interval(&config, &pubkey, min_update_interval_slots)
+        }
+        ("set-request-fee", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let request_fee = value_t!(arg_matches, "request_fee", u64).unwrap();
+            command_set_request_fee(&config, &pubkey, request_fee)
+        }
+        ("withdraw-fees", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let destination = pubkey_of(arg_matches, "destination");
+            command_withdraw_fees(&config, &pubkey, destination)
+        }
+        ("create-subscription", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            command_create_subscription(&config, &pubkey)
+        }
+        ("fund-subscription", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let amount = value_t!(arg_matches, "amount", u64).unwrap();
+            command_fund_subscription(&config, &pubkey, amount)
+        }
+        ("cancel-subscription", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let destination = pubkey_of(arg_matches, "destination");
+            command_cancel_subscription(&config, &pubkey, destination)
+        }
+        ("slash", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            command_slash_operator(&config, &pubkey)
+        }
+        ("pause", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            command_pause_randomness_oracle(&config, &pubkey)
+        }
+        ("resume", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            command_resume_randomness_oracle(&config, &pubkey)
+        }
+        ("init-config", Some(arg_matches)) => {
+            let admin = pubkey_of(arg_matches, "admin").unwrap();
+            command_init_program_config(&config, &admin)
+        }
+        ("approve", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            command_approve_oracle(&config, &pubkey)
+        }
+        ("revoke", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            command_revoke_oracle(&config, &pubkey)
+        }
+        ("cancel-request", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let destination = pubkey_of(arg_matches, "destination");
+            command_cancel_randomness_request(&config, &pubkey, destination)
+        }
+        ("propose-admin", Some(arg_matches)) => {
+            let new_admin = pubkey_of(arg_matches, "new_admin").unwrap();
+            command_propose_admin(&config, &new_admin)
+        }
+        ("accept-admin", Some(_)) => command_accept_admin(&config),
+        ("propose-authority", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let new_authority = pubkey_of(arg_matches, "new_authority").unwrap();
+            command_propose_authority(&config, &pubkey, &new_authority)
+        }
+        ("accept-authority", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            command_accept_authority(&config, &pubkey)
+        }
+        ("migrate", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let keypair = keypair_of(arg_matches, "keypair");
+            command_migrate_randomness_oracle(&config, &pubkey, keypair)
+        }
+        ("close", Some(arg_matches)) => {
+            let pubkey = pubkey_of(arg_matches, "pubkey").unwrap();
+            let destination = pubkey_of(arg_matches, "destination");
+            command_close_randomness_oracle(&config, &pubkey, destination)
         }
         _ => unreachable!(),
     }
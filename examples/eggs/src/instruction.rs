@@ -16,6 +16,8 @@ pub enum EggsInstruction {
     /// [W] Egg - uninitialized
     /// [R] Randomness oracle
     /// [R] Rent sysvar
+    /// [R] Clock sysvar
+    /// [R] Instructions sysvar
     CreateEgg,
 }
 
@@ -26,6 +28,8 @@ pub fn create_egg(program_id: &Pubkey, egg: &Pubkey, randomness_oracle: &Pubkey)
         AccountMeta::new(*egg, false),
         AccountMeta::new_readonly(*randomness_oracle, false),
         AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
     ];
 
     Instruction::new_with_borsh(*program_id, &EggsInstruction::CreateEgg, accounts)
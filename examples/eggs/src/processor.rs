@@ -6,9 +6,10 @@ use crate::{
     utils::*,
 };
 use borsh::BorshDeserialize;
-use randomness_oracle_program::read_value;
+use randomness_oracle_program::{assert_no_prior_update, read_value_settled};
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program_pack::Pack,
@@ -27,6 +28,9 @@ impl Processor {
         let randomness_oracle_info = next_account_info(account_info_iter)?;
         let rent_info = next_account_info(account_info_iter)?;
         let rent = &Rent::from_account_info(rent_info)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = &Clock::from_account_info(clock_info)?;
+        let instructions_info = next_account_info(account_info_iter)?;
 
         assert_rent_exempt(rent, egg_info)?;
         assert_owned_by(egg_info, program_id)?;
@@ -35,7 +39,9 @@ impl Processor {
         let mut egg = Egg::unpack_unchecked(&egg_info.data.borrow())?;
         assert_uninitialized(&egg)?;
 
-        let (gen, _) = read_value(randomness_oracle_info)?;
+        // Randomness oracle value must be settled before this slot and transaction
+        assert_no_prior_update(randomness_oracle_info, instructions_info)?;
+        let (gen, _, _) = read_value_settled(randomness_oracle_info, clock)?;
 
         egg.init(InitEggParams { gen });
 
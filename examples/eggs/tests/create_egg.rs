@@ -2,11 +2,15 @@
 
 mod utils;
 
-use solana_program::pubkey::Pubkey;
+use randomness_oracle_program::error::RandomnessOracleError;
+use solana_program::{instruction::InstructionError, pubkey::Pubkey};
 use solana_program_test::*;
+use solana_sdk::transaction::TransactionError;
 use std::str::FromStr;
 use utils::*;
 
+const ORACLE_FIXTURE: &str = "FjvDD58C8Su9Uq92dztpUpAkoY9dzAf3HiwUxbpMkcru";
+
 const ORACLE_VALUE: [u8; 32] = [
     21, 55, 34, 118, 215, 173, 121, 153, 252, 95, 48, 178, 57, 189, 58, 113, 84, 254, 7, 95, 122,
     136, 28, 185, 222, 127, 206, 122, 239, 245, 101, 22,
@@ -14,11 +18,11 @@ const ORACLE_VALUE: [u8; 32] = [
 
 async fn setup() -> (ProgramTestContext, TestOracle) {
     let mut test = program_test();
-    let oracle = add_oracle(
-        &mut test,
-        Pubkey::from_str("FjvDD58C8Su9Uq92dztpUpAkoY9dzAf3HiwUxbpMkcru").unwrap(),
-    );
-    let context = test.start_with_context().await;
+    let oracle = add_oracle(&mut test, Pubkey::from_str(ORACLE_FIXTURE).unwrap());
+    let mut context = test.start_with_context().await;
+
+    // Randomness oracle fixture has layout version 1 and is updated at slot 201
+    context.warp_to_slot(300).unwrap();
 
     (context, oracle)
 }
@@ -34,3 +38,42 @@ async fn success() {
 
     assert_eq!(egg.gen, ORACLE_VALUE);
 }
+
+#[tokio::test]
+async fn fail_forged_oracle() {
+    let mut test = program_test();
+    let test_oracle = add_forged_oracle(
+        &mut test,
+        Pubkey::new_unique(),
+        Pubkey::from_str(ORACLE_FIXTURE).unwrap(),
+    );
+    let mut context = test.start_with_context().await;
+
+    let test_egg = TestEgg::new();
+    assert_eq!(
+        test_egg
+            .create(&mut context, &test_oracle)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(1, InstructionError::IllegalOwner)
+    );
+}
+
+#[tokio::test]
+async fn fail_oracle_written_in_same_transaction() {
+    let (mut context, test_oracle) = setup().await;
+
+    let test_egg = TestEgg::new();
+    assert_eq!(
+        test_egg
+            .create_after_oracle_write(&mut context, &test_oracle)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        TransactionError::InstructionError(
+            2,
+            InstructionError::Custom(RandomnessOracleError::UpdatedInTransaction as u32)
+        )
+    );
+}
@@ -49,4 +49,31 @@ impl TestEgg {
 
         context.banks_client.process_transaction(tx).await
     }
+
+    pub async fn create_after_oracle_write(
+        &self,
+        context: &mut ProgramTestContext,
+        oracle: &TestOracle,
+    ) -> transport::Result<()> {
+        let rent = context.banks_client.get_rent().await.unwrap();
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                system_instruction::create_account(
+                    &context.payer.pubkey(),
+                    &self.keypair.pubkey(),
+                    rent.minimum_balance(Egg::LEN),
+                    Egg::LEN as u64,
+                    &id(),
+                ),
+                // Any instruction taking randomness oracle as writable
+                system_instruction::transfer(&context.payer.pubkey(), &oracle.pubkey, 1),
+                instruction::create_egg(&id(), &self.keypair.pubkey(), &oracle.pubkey),
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, &self.keypair],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
 }
@@ -27,3 +27,15 @@ pub fn add_oracle(test: &mut ProgramTest, pubkey: Pubkey) -> TestOracle {
 
     TestOracle { pubkey }
 }
+
+pub fn add_forged_oracle(test: &mut ProgramTest, pubkey: Pubkey, fixture: Pubkey) -> TestOracle {
+    // Same data as randomness oracle fixture, but owned by some other program
+    test.add_account_with_file_data(
+        pubkey,
+        u32::MAX as u64,
+        Pubkey::new_unique(),
+        &format!("{}.bin", fixture.to_string()),
+    );
+
+    TestOracle { pubkey }
+}
@@ -0,0 +1,160 @@
+//! Native Ed25519 program helpers.
+
+use crate::error::RandomnessOracleError;
+use solana_program::{instruction::Instruction, program_error::ProgramError, pubkey::Pubkey};
+use std::convert::TryInto;
+
+solana_program::declare_id!("Ed25519SigVerify111111111111111111111111111");
+
+/// Size of serialized signature offsets.
+pub const SIGNATURE_OFFSETS_SERIALIZED_SIZE: usize = 14;
+/// Start of signature offsets in instruction data.
+pub const SIGNATURE_OFFSETS_START: usize = 2;
+/// Size of ed25519 public key.
+pub const PUBKEY_SERIALIZED_SIZE: usize = 32;
+/// Size of ed25519 signature.
+pub const SIGNATURE_SERIALIZED_SIZE: usize = 64;
+
+/// Offsets of a single signature in the native Ed25519 program instruction data.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Ed25519SignatureOffsets {
+    /// Offset to ed25519 signature of 64 bytes.
+    pub signature_offset: u16,
+    /// Instruction index to find signature.
+    pub signature_instruction_index: u16,
+    /// Offset to public key of 32 bytes.
+    pub public_key_offset: u16,
+    /// Instruction index to find public key.
+    pub public_key_instruction_index: u16,
+    /// Offset to start of message data.
+    pub message_data_offset: u16,
+    /// Size of message data.
+    pub message_data_size: u16,
+    /// Instruction index to find message data.
+    pub message_instruction_index: u16,
+}
+
+impl Ed25519SignatureOffsets {
+    fn pack(&self) -> Vec<u8> {
+        [
+            self.signature_offset,
+            self.signature_instruction_index,
+            self.public_key_offset,
+            self.public_key_instruction_index,
+            self.message_data_offset,
+            self.message_data_size,
+            self.message_instruction_index,
+        ]
+        .iter()
+        .flat_map(|value| value.to_le_bytes().to_vec())
+        .collect()
+    }
+
+    fn unpack(src: &[u8]) -> Result<Self, ProgramError> {
+        if src.len() < SIGNATURE_OFFSETS_SERIALIZED_SIZE {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let read =
+            |index: usize| u16::from_le_bytes(src[index * 2..index * 2 + 2].try_into().unwrap());
+
+        Ok(Self {
+            signature_offset: read(0),
+            signature_instruction_index: read(1),
+            public_key_offset: read(2),
+            public_key_instruction_index: read(3),
+            message_data_offset: read(4),
+            message_data_size: read(5),
+            message_instruction_index: read(6),
+        })
+    }
+}
+
+/// Creates native Ed25519 program instruction verifying a single signature.
+///
+/// `instruction_index` is the position of this instruction in the transaction,
+/// signature, public key and message are all stored in its own data.
+pub fn new_ed25519_instruction(
+    pubkey: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+    instruction_index: u16,
+) -> Instruction {
+    let public_key_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_SERIALIZED_SIZE;
+    let signature_offset = public_key_offset + PUBKEY_SERIALIZED_SIZE;
+    let message_data_offset = signature_offset + SIGNATURE_SERIALIZED_SIZE;
+
+    let offsets = Ed25519SignatureOffsets {
+        signature_offset: signature_offset as u16,
+        signature_instruction_index: instruction_index,
+        public_key_offset: public_key_offset as u16,
+        public_key_instruction_index: instruction_index,
+        message_data_offset: message_data_offset as u16,
+        message_data_size: message.len() as u16,
+        message_instruction_index: instruction_index,
+    };
+
+    let mut data = Vec::with_capacity(message_data_offset + message.len());
+    // Number of signatures and padding
+    data.extend_from_slice(&[1, 0]);
+    data.extend_from_slice(&offsets.pack());
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: id(),
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Verify that native Ed25519 program instruction at `instruction_index` checks
+/// exactly one `signature` of `message` by `pubkey` stored in its own data.
+pub fn verify_ed25519_instruction(
+    instruction: &Instruction,
+    instruction_index: u16,
+    pubkey: &Pubkey,
+    signature: &[u8; 64],
+    message: &[u8],
+) -> Result<(), ProgramError> {
+    if !check_id(&instruction.program_id) {
+        return Err(RandomnessOracleError::InvalidSignatureVerification.into());
+    }
+
+    let data = &instruction.data;
+    if data.first() != Some(&1) {
+        return Err(RandomnessOracleError::InvalidSignatureVerification.into());
+    }
+
+    let offsets = Ed25519SignatureOffsets::unpack(
+        data.get(SIGNATURE_OFFSETS_START..)
+            .ok_or(ProgramError::InvalidInstructionData)?,
+    )?;
+
+    // Data must be taken from the Ed25519 instruction itself
+    let is_own_index = |index: u16| index == instruction_index || index == u16::MAX;
+    if !is_own_index(offsets.signature_instruction_index)
+        || !is_own_index(offsets.public_key_instruction_index)
+        || !is_own_index(offsets.message_instruction_index)
+    {
+        return Err(RandomnessOracleError::InvalidSignatureVerification.into());
+    }
+
+    let slice = |offset: u16, size: usize| {
+        data.get(offset as usize..offset as usize + size)
+            .ok_or(ProgramError::InvalidInstructionData)
+    };
+
+    if slice(offsets.public_key_offset, PUBKEY_SERIALIZED_SIZE)? != pubkey.as_ref()
+        || slice(offsets.signature_offset, SIGNATURE_SERIALIZED_SIZE)? != signature
+        || slice(
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )? != message
+    {
+        return Err(RandomnessOracleError::InvalidSignatureVerification.into());
+    }
+
+    Ok(())
+}
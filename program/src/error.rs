@@ -0,0 +1,231 @@
+//! Error types.
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+use solana_program::{
+    decode_error::DecodeError,
+    msg,
+    program_error::{PrintProgramError, ProgramError},
+};
+use thiserror::Error;
+
+/// Errors that may be returned by the program.
+#[derive(Clone, Debug, Eq, Error, FromPrimitive, PartialEq)]
+pub enum RandomnessOracleError {
+    /// Signer is not the randomness oracle authority.
+    #[error("Randomness oracle authority mismatch")]
+    InvalidAuthority,
+
+    /// Randomness oracle is already initialized.
+    #[error("Randomness oracle already initialized")]
+    AlreadyInitialized,
+
+    /// Randomness oracle value is too old.
+    #[error("Randomness oracle value is stale")]
+    StaleValue,
+
+    /// Account data length does not match the expected layout.
+    #[error("Wrong account size")]
+    WrongAccountSize,
+
+    /// Account does not hold enough lamports to be rent exempt.
+    #[error("Account is not rent exempt")]
+    NotRentExempt,
+
+    /// Account data can not be deserialized.
+    #[error("Failed to deserialize account data")]
+    DeserializationFailed,
+
+    /// Passed account is not the expected sysvar.
+    #[error("Invalid sysvar account")]
+    InvalidSysvar,
+
+    /// Commitment is pending and must be revealed first.
+    #[error("Commitment already pending")]
+    CommitmentAlreadyPending,
+
+    /// There is no commitment to reveal.
+    #[error("No pending commitment")]
+    NoPendingCommitment,
+
+    /// Commitment hash is not valid.
+    #[error("Invalid commitment")]
+    InvalidCommitment,
+
+    /// Revealed seed does not hash to the commitment.
+    #[error("Seed does not match the commitment")]
+    CommitmentMismatch,
+
+    /// Reveal is sent in the same slot as the commitment.
+    #[error("Reveal must happen after the commitment slot")]
+    RevealTooEarly,
+
+    /// Ed25519 signature verification instruction is missing or does not match.
+    #[error("Invalid signature verification")]
+    InvalidSignatureVerification,
+
+    /// Signer is not the proposed authority.
+    #[error("Pending authority mismatch")]
+    InvalidPendingAuthority,
+
+    /// Randomness oracle is closed.
+    #[error("Randomness oracle is closed")]
+    OracleClosed,
+
+    /// Account is not a randomness oracle.
+    #[error("Invalid account type")]
+    InvalidAccountType,
+
+    /// Randomness oracle value was written in the current slot.
+    #[error("Randomness oracle value is not settled")]
+    ValueNotSettled,
+
+    /// Randomness oracle is written by a preceding instruction of the transaction.
+    #[error("Randomness oracle is updated in the same transaction")]
+    UpdatedInTransaction,
+
+    /// History account does not belong to randomness oracle.
+    #[error("Invalid randomness oracle history")]
+    InvalidHistory,
+
+    /// Randomness oracle already has a history account.
+    #[error("Randomness oracle history already set")]
+    HistoryAlreadySet,
+
+    /// Requested slot is older than the kept history.
+    #[error("Randomness oracle history expired")]
+    HistoryExpired,
+
+    /// No value is published at or after the requested slot yet.
+    #[error("Randomness oracle value is not published yet")]
+    ValueNotPublished,
+
+    /// Minimum update interval has not passed since the last update.
+    #[error("Randomness oracle update is too early")]
+    UpdateTooEarly,
+
+    /// Metadata name or URI is too long.
+    #[error("Invalid randomness oracle metadata")]
+    InvalidMetadata,
+
+    /// Randomness request doesn't belong to the randomness oracle.
+    #[error("Invalid randomness request")]
+    InvalidRandomnessRequest,
+
+    /// Randomness request has already been fulfilled.
+    #[error("Randomness request already fulfilled")]
+    RequestAlreadyFulfilled,
+
+    /// Callback program or accounts don't match the randomness request.
+    #[error("Invalid randomness request callback")]
+    InvalidCallback,
+
+    /// Randomness request has not been fulfilled yet.
+    #[error("Randomness request not fulfilled")]
+    RequestNotFulfilled,
+
+    /// Treasury doesn't belong to the randomness oracle.
+    #[error("Invalid treasury")]
+    InvalidTreasury,
+
+    /// Subscription doesn't belong to the randomness oracle or requester.
+    #[error("Invalid subscription")]
+    InvalidSubscription,
+
+    /// Subscription balance doesn't cover the request fee.
+    #[error("Insufficient subscription balance")]
+    InsufficientSubscriptionBalance,
+
+    /// Bond vault doesn't belong to the randomness oracle.
+    #[error("Invalid bond vault")]
+    InvalidBondVault,
+
+    /// Commitment can still be revealed.
+    #[error("Reveal deadline has not passed")]
+    RevealNotExpired,
+
+    /// Randomness oracle is paused by authority.
+    #[error("Randomness oracle is paused")]
+    OraclePaused,
+
+    /// Program data account doesn't belong to the program.
+    #[error("Invalid program data")]
+    InvalidProgramData,
+
+    /// Program config is not the program config address.
+    #[error("Invalid program config")]
+    InvalidProgramConfig,
+
+    /// Randomness oracle is not approved by the program config admin.
+    #[error("Randomness oracle is not approved")]
+    OracleNotApproved,
+
+    /// Registry is not the registry of the randomness oracle authority.
+    #[error("Invalid registry")]
+    InvalidRegistry,
+
+    /// Registry has no room for another randomness oracle.
+    #[error("Registry is full, an authority can have at most 64 randomness oracles")]
+    RegistryFull,
+
+    /// Instruction doesn't match randomness oracle update mode.
+    #[error("Update is not allowed by randomness oracle update mode")]
+    InvalidUpdateMode,
+
+    /// Name of randomness oracle with address derived from it can't be changed.
+    #[error("Randomness oracle name is immutable")]
+    ImmutableName,
+
+    /// Randomness request can't be cancelled before it expires.
+    #[error("Randomness request has not expired")]
+    RequestNotExpired,
+
+    /// Subscription can't be cancelled while its fees are reserved.
+    #[error("Subscription has pending randomness requests")]
+    SubscriptionHasPendingRequests,
+
+    /// Request queue is not the request queue of the randomness oracle.
+    #[error("Invalid request queue")]
+    InvalidRequestQueue,
+
+    /// Request queue has no room for another randomness request.
+    #[error("Request queue is full, a randomness oracle can have at most 64 pending requests")]
+    RequestQueueFull,
+
+    /// Randomness oracle can't be closed while requests are pending.
+    #[error("Randomness oracle has pending requests")]
+    RequestQueueNotEmpty,
+
+    /// Slot hash isn't in the `SlotHashes` sysvar, the slot is either too recent or too old.
+    #[error("Slot hash is not available")]
+    SlotHashNotFound,
+
+    /// Too many randomness oracles updated in one instruction.
+    #[error("Too many updates, at most 8 randomness oracles can be updated in one instruction")]
+    TooManyUpdates,
+
+    /// Bond is below `MIN_BOND_LAMPORTS` or the rent exempt minimum of the bond vault.
+    #[error("Bond is below the minimum")]
+    InsufficientBond,
+}
+
+impl From<RandomnessOracleError> for ProgramError {
+    fn from(e: RandomnessOracleError) -> Self {
+        ProgramError::Custom(e as u32)
+    }
+}
+
+impl<T> DecodeError<T> for RandomnessOracleError {
+    fn type_of() -> &'static str {
+        "RandomnessOracleError"
+    }
+}
+
+impl PrintProgramError for RandomnessOracleError {
+    fn print<E>(&self)
+    where
+        E: 'static + std::error::Error + DecodeError<E> + PrintProgramError + FromPrimitive,
+    {
+        msg!(&self.to_string());
+    }
+}
@@ -0,0 +1,110 @@
+//! Program events emitted through the program logs.
+
+use crate::state::RandomnessOracle;
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{clock::Slot, msg, pubkey::Pubkey};
+
+/// Prefix of the program log message carrying a base64 encoded event.
+pub const EVENT_LOG_PREFIX: &str = "RandomnessOracleEvent: ";
+
+/// Prefix the runtime adds to messages logged by the program.
+const PROGRAM_LOG_PREFIX: &str = "Program log: ";
+
+/// Randomness oracle event kind.
+#[derive(Debug, Clone, Copy, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq)]
+pub enum RandomnessOracleEventKind {
+    /// Randomness oracle initialized.
+    Initialized,
+    /// Random value updated.
+    Updated,
+    /// Authority changed.
+    AuthorityChanged,
+    /// Randomness oracle closed.
+    Closed,
+}
+
+/// Randomness oracle event.
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq)]
+pub struct RandomnessOracleEvent {
+    /// Event kind.
+    pub kind: RandomnessOracleEventKind,
+    /// Random oracle.
+    pub randomness_oracle: Pubkey,
+    /// Round of the random value.
+    pub round: u64,
+    /// Slot of the random value.
+    pub slot: Slot,
+    /// Random value.
+    pub value: [u8; 32],
+    /// Authority.
+    pub authority: Pubkey,
+}
+
+impl RandomnessOracleEvent {
+    /// Create event from the randomness oracle state.
+    pub fn new(
+        kind: RandomnessOracleEventKind,
+        randomness_oracle: &Pubkey,
+        state: &RandomnessOracle,
+    ) -> Self {
+        Self {
+            kind,
+            randomness_oracle: *randomness_oracle,
+            round: state.round,
+            slot: state.slot,
+            value: state.value,
+            authority: state.authority,
+        }
+    }
+
+    /// Log message carrying the event.
+    pub fn to_log_message(&self) -> String {
+        let mut data = Vec::new();
+        self.serialize(&mut data).unwrap();
+        format!("{}{}", EVENT_LOG_PREFIX, base64::encode(data))
+    }
+
+    /// Parse event from the log message.
+    pub fn from_log_message(message: &str) -> Option<Self> {
+        let data = base64::decode(message.strip_prefix(EVENT_LOG_PREFIX)?).ok()?;
+        Self::try_from_slice(&data).ok()
+    }
+
+    /// Emit event to the program logs.
+    pub fn emit(&self) {
+        msg!(&self.to_log_message());
+    }
+}
+
+/// Parse events emitted by the program from transaction log messages.
+///
+/// Invocations are tracked so that only messages logged by the program itself are parsed,
+/// other programs can't forge events by logging the same prefix.
+pub fn parse_events(program_id: &Pubkey, log_messages: &[String]) -> Vec<RandomnessOracleEvent> {
+    let program_id = program_id.to_string();
+    let mut invoke_stack: Vec<&str> = Vec::new();
+    let mut events = Vec::new();
+
+    for message in log_messages {
+        if let Some(log) = message.strip_prefix(PROGRAM_LOG_PREFIX) {
+            if invoke_stack.last() == Some(&program_id.as_str()) {
+                events.extend(RandomnessOracleEvent::from_log_message(log));
+            }
+            continue;
+        }
+
+        // Program <id> invoke [<depth>], Program <id> success, Program <id> failed: <error>
+        let mut words = message.split_whitespace();
+        if let (Some("Program"), Some(id), Some(status)) =
+            (words.next(), words.next(), words.next())
+        {
+            if status == "invoke" {
+                invoke_stack.push(id);
+            } else if status == "success" || status.starts_with("failed") {
+                invoke_stack.pop();
+            }
+        }
+    }
+
+    events
+}
@@ -1,10 +1,18 @@
 //! Instruction states definitions.
 
+use crate::{
+    find_bond_vault_address, find_program_config_address, find_randomness_oracle_address,
+    find_registry_address, find_request_queue_address, find_subscription_address,
+    find_treasury_address,
+    state::{CallbackAccount, RandomnessOracleMetadata, UpdateMode},
+};
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{
+    bpf_loader_upgradeable,
     instruction::{AccountMeta, Instruction},
+    message::Message,
     pubkey::Pubkey,
-    sysvar,
+    system_program, sysvar,
 };
 
 /// Instructions supported by the program.
@@ -12,11 +20,24 @@ use solana_program::{
 pub enum RandomnessOracleInstruction {
     /// Initializes a new randomness oracle.
     ///
+    /// An authority can have at most `MAX_REGISTRY_ORACLES` (64) randomness oracles,
+    /// initialization fails with `RegistryFull` once its registry is full.
+    ///
     /// Accounts:
-    /// [W] Randomness oracle - off-chain created account.
-    /// [RS] Authority - randomness oracle authority to update state.
+    /// [W] Randomness oracle - off-chain created rent exempt account.
+    /// [WS] Authority - randomness oracle authority to update state,
+    /// pays the bond of at least `MIN_BOND_LAMPORTS`.
     /// [R] Clock sysvar.
-    InitRandomnessOracle,
+    /// [R] Rent sysvar.
+    /// [W] Bond vault - randomness oracle bond vault.
+    /// [R] System program.
+    /// [W] Registry - `find_registry_address(authority)`, created on first use.
+    InitRandomnessOracle {
+        min_update_interval_slots: u64,
+        metadata: RandomnessOracleMetadata,
+        bond: u64,
+        update_mode: UpdateMode,
+    },
 
     /// Updates randomness oracle.
     ///
@@ -24,7 +45,374 @@ pub enum RandomnessOracleInstruction {
     /// [W] Randomness oracle - account.
     /// [RS] Authority - randomness oracle authority to update state.
     /// [R] Clock sysvar.
+    /// [W] History - randomness oracle history, required if history is kept.
     UpdateRandomnessOracle { value: [u8; 32] },
+
+    /// Commits to the hash of the next randomness oracle value in `CommitReveal` mode,
+    /// of the submission in `Mix` mode or of the signature in `Signature` mode.
+    ///
+    /// Minimum update interval is checked at commit, the commitment should be revealed
+    /// within `MAX_REVEAL_DELAY_SLOTS` after it, otherwise the operator can be slashed.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority to update state.
+    /// [R] Clock sysvar.
+    CommitRandomness { hash: [u8; 32] },
+
+    /// Reveals the seed of the pending commitment and publishes it as a new value.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority to update state.
+    /// [R] Clock sysvar.
+    /// [W] History - randomness oracle history, required if history is kept.
+    RevealRandomness { seed: [u8; 32] },
+
+    /// Reveals the value of the pending commitment and updates randomness oracle with
+    /// `hash(value, previous value, commitment slot hash)`.
+    ///
+    /// The value is fixed by the commitment before the hash of the commitment slot is known,
+    /// so neither the authority nor the validator producing the slot alone control the result.
+    /// The authority can only withhold the reveal, which gets its bond slashed.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority to update state.
+    /// [R] Clock sysvar.
+    /// [R] SlotHashes sysvar.
+    /// [W] History - randomness oracle history, required if history is kept.
+    MixRandomnessOracle { value: [u8; 32] },
+
+    /// Reveals the signature of the pending commitment and updates randomness oracle with
+    /// `hash(signature, commitment slot hash)`, where signature is made by authority
+    /// over `RandomnessOracle::signature_message` and verified by the preceding
+    /// native Ed25519 program instruction.
+    ///
+    /// Ed25519 signatures aren't unique, but the authority commits to `hash(signature)`
+    /// before the hash of the commitment slot is known, so it can't pick the signature
+    /// with the most suitable result. The authority can only withhold the reveal,
+    /// which gets its bond slashed.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority to update state.
+    /// [R] Clock sysvar.
+    /// [R] Instructions sysvar.
+    /// [R] SlotHashes sysvar.
+    /// [W] History - randomness oracle history, required if history is kept.
+    UpdateRandomnessOracleWithSignature { signature: [u8; 64] },
+
+    /// Proposes a new authority, which has to accept it with `AcceptAuthority`.
+    /// Default pubkey cancels the current proposal.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - current randomness oracle authority.
+    ProposeAuthority { new_authority: Pubkey },
+
+    /// Accepts the proposed authority and moves randomness oracle to its registry.
+    /// Fails with `RegistryFull` if the new authority has `MAX_REGISTRY_ORACLES` (64) oracles.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [WS] New authority - proposed randomness oracle authority, pays for its registry.
+    /// [W] Registry - registry of the current authority.
+    /// [W] New registry - registry of the new authority, created on first use.
+    /// [R] Rent sysvar.
+    /// [R] System program.
+    AcceptAuthority,
+
+    /// Closes randomness oracle, its history and request queue,
+    /// and transfers their lamports, bond and collected fees to destination.
+    /// Fails while requests are pending, expired ones can be cancelled first.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority.
+    /// [W] Destination - account to receive lamports.
+    /// [W] Bond vault - randomness oracle bond vault.
+    /// [R] System program.
+    /// [W] Registry - registry of the authority.
+    /// [W] Treasury - `find_treasury_address(randomness oracle)`.
+    /// [W] Request queue - `find_request_queue_address(randomness oracle)`.
+    /// [W] History - randomness oracle history, required if history is kept.
+    CloseRandomnessOracle,
+
+    /// Initializes randomness oracle history keeping the latest published values.
+    /// Current value of randomness oracle becomes the first entry.
+    ///
+    /// Accounts:
+    /// [W] History - off-chain created rent exempt account.
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority.
+    /// [R] Rent sysvar.
+    InitRandomnessOracleHistory,
+
+    /// Sets the minimum number of slots between updates.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority.
+    SetMinUpdateInterval { min_update_interval_slots: u64 },
+
+    /// Creates and initializes a new randomness oracle
+    /// at the address derived from authority and metadata name.
+    ///
+    /// An authority can have at most `MAX_REGISTRY_ORACLES` (64) randomness oracles,
+    /// creation fails with `RegistryFull` once its registry is full.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - `find_randomness_oracle_address(authority, name)`.
+    /// [WS] Authority - randomness oracle authority, pays for the account
+    /// and the bond of at least `MIN_BOND_LAMPORTS`.
+    /// [R] Clock sysvar.
+    /// [R] Rent sysvar.
+    /// [R] System program.
+    /// [W] Bond vault - randomness oracle bond vault.
+    /// [W] Registry - `find_registry_address(authority)`, created on first use.
+    CreateRandomnessOracle {
+        metadata: RandomnessOracleMetadata,
+        min_update_interval_slots: u64,
+        bond: u64,
+        update_mode: UpdateMode,
+    },
+
+    /// Migrates version 1 randomness oracle into a new account of the current layout
+    /// and closes the old one.
+    ///
+    /// Version 1 accounts are smaller and can't be resized, so the migrated oracle
+    /// gets a new address and consumers have to switch to it. Version 1 accounts are
+    /// readable, but have to be migrated to be updated. Later versions are upgraded
+    /// in place and keep their address.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account of an older layout version.
+    /// [W] New randomness oracle - off-chain created rent exempt account.
+    /// [WS] Authority - randomness oracle authority, receives lamports of the old account.
+    /// [R] Rent sysvar.
+    /// [W] Registry - `find_registry_address(authority)`, created on first use.
+    /// [R] System program.
+    MigrateRandomnessOracle,
+
+    /// Updates randomness oracle metadata, changed metadata drops the admin approval.
+    ///
+    /// Name of the oracle created by `CreateRandomnessOracle` can't be changed,
+    /// as its address is derived from it.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority.
+    UpdateMetadata { metadata: RandomnessOracleMetadata },
+
+    /// Requests a random value for the requester.
+    ///
+    /// Once fulfilled, callback program is invoked with callback data and accounts:
+    /// [R] Randomness request - account, read the value with `read_request_value`.
+    /// ... Callback accounts.
+    ///
+    /// Callback program has to check that the request was made by the expected
+    /// requester, since anyone can invoke it with the same accounts.
+    ///
+    /// Requester pays the randomness oracle request fee into its treasury, unless
+    /// the fee is reserved in the requester subscription and paid on fulfillment.
+    ///
+    /// Request is queued until fulfilled or cancelled, a randomness oracle can have
+    /// at most `MAX_QUEUE_REQUESTS` (64) pending requests.
+    ///
+    /// Accounts:
+    /// [W] Randomness request - off-chain created rent exempt account.
+    /// [R] Randomness oracle - account.
+    /// [WS] Requester - randomness request owner, pays the request fee.
+    /// [R] Clock sysvar.
+    /// [R] Rent sysvar.
+    /// [W] Treasury - randomness oracle treasury.
+    /// [R] System program.
+    /// [W] Request queue - `find_request_queue_address(randomness oracle)`,
+    ///     created on first use, paid by the requester.
+    /// [W] Subscription - requester subscription, optional.
+    RequestRandomness {
+        seed: [u8; 32],
+        callback_program: Option<Pubkey>,
+        callback_accounts: Vec<CallbackAccount>,
+        callback_data: Vec<u8>,
+    },
+
+    /// Fulfills randomness request with the value derived from the hash of the request slot,
+    /// request seed and request address, then invokes the callback program.
+    ///
+    /// Slot hash is unknown when the request is made and the authority doesn't provide
+    /// the value, so it can't pick a value that suits it. The request has to be fulfilled
+    /// after its slot while the slot is still in `SlotHashes` sysvar. Requests left unfulfilled
+    /// can be cancelled with `CancelRandomnessRequest`.
+    ///
+    /// Accounts:
+    /// [W] Randomness request - account.
+    /// [R] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority.
+    /// [R] Clock sysvar.
+    /// [R] SlotHashes sysvar.
+    /// [W] Request queue - `find_request_queue_address(randomness oracle)`.
+    /// [W] Subscription - required if the request is paid by subscription.
+    /// [W] Treasury - randomness oracle treasury, required if the request is paid by subscription.
+    /// [R] Callback program - required if the request has a callback.
+    /// ... Callback accounts - in the order of the request.
+    FulfillRandomness,
+
+    /// Sets the fee paid for each randomness request.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority.
+    SetRequestFee { request_fee: u64 },
+
+    /// Withdraws all collected fees from the treasury.
+    ///
+    /// Accounts:
+    /// [R] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority.
+    /// [W] Treasury - randomness oracle treasury.
+    /// [W] Destination - account to receive lamports.
+    /// [R] System program.
+    WithdrawFees,
+
+    /// Creates requester subscription to randomness oracle.
+    ///
+    /// Accounts:
+    /// [W] Subscription - uninitialized program derived address for randomness oracle and owner.
+    /// [R] Randomness oracle - account.
+    /// [WS] Owner - subscription owner, pays for the account.
+    /// [R] Rent sysvar.
+    /// [R] System program.
+    CreateSubscription,
+
+    /// Deposits lamports to the subscription balance.
+    ///
+    /// Accounts:
+    /// [W] Subscription - account.
+    /// [WS] Funder - account to transfer lamports from.
+    /// [R] System program.
+    FundSubscription { amount: u64 },
+
+    /// Closes subscription and returns all its lamports.
+    /// Subscription with pending requests can't be closed, they have to be fulfilled or cancelled.
+    ///
+    /// Accounts:
+    /// [W] Subscription - account.
+    /// [RS] Owner - subscription owner.
+    /// [W] Destination - account to receive lamports.
+    CancelSubscription,
+
+    /// Slashes operator bond for the commitment not revealed in `MAX_REVEAL_DELAY_SLOTS`,
+    /// transferring `1 / SLASH_DIVISOR` of the bond to the caller and dropping the commitment.
+    /// Bond that would be left below the rent exempt minimum is transferred whole.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [W] Bond vault - randomness oracle bond vault.
+    /// [WS] Caller - account to receive slashed lamports.
+    /// [R] Clock sysvar.
+    /// [R] System program.
+    /// [R] Rent sysvar.
+    SlashOperator,
+
+    /// Pauses randomness oracle, updates are rejected and value can't be read until resumed.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority.
+    PauseRandomnessOracle,
+
+    /// Resumes paused randomness oracle.
+    ///
+    /// Accounts:
+    /// [W] Randomness oracle - account.
+    /// [RS] Authority - randomness oracle authority.
+    ResumeRandomnessOracle,
+
+    /// Initializes program config, only the program upgrade authority can do it.
+    ///
+    /// Accounts:
+    /// [W] Program config - uninitialized program derived account.
+    /// [WS] Upgrade authority - program upgrade authority, pays for the account.
+    /// [R] Program data - program data account of the program.
+    /// [R] Rent sysvar.
+    /// [R] System program.
+    InitProgramConfig { admin: Pubkey },
+
+    /// Marks randomness oracle as vetted by the program config admin.
+    ///
+    /// Accounts:
+    /// [R] Program config - program config account.
+    /// [RS] Admin - program config admin.
+    /// [W] Randomness oracle - account.
+    ApproveOracle,
+
+    /// Revokes randomness oracle approval.
+    ///
+    /// Accounts:
+    /// [R] Program config - program config account.
+    /// [RS] Admin - program config admin.
+    /// [W] Randomness oracle - account.
+    RevokeOracle,
+
+    /// Updates multiple randomness oracles of the same authority,
+    /// at most `MAX_BATCH_UPDATES` in one instruction.
+    ///
+    /// Accounts:
+    /// [RS] Authority - randomness oracles authority to update state.
+    /// [R] Clock sysvar.
+    /// For each value:
+    /// [W] Randomness oracle - account.
+    /// [W] History - randomness oracle history, required if history is kept.
+    UpdateRandomnessOracles { values: Vec<[u8; 32]> },
+
+    /// Proposes a new program config admin, the proposed admin has to accept it.
+    ///
+    /// Accounts:
+    /// [W] Program config - program config account.
+    /// [RS] Admin - program config admin.
+    ProposeAdmin { new_admin: Pubkey },
+
+    /// Accepts program config admin role by the proposed admin.
+    ///
+    /// Accounts:
+    /// [W] Program config - program config account.
+    /// [RS] New admin - proposed program config admin.
+    AcceptAdmin,
+
+    /// Cancels randomness request not fulfilled within `REQUEST_EXPIRY_SLOTS`
+    /// and returns its lamports. Fee reserved in the subscription is returned to its balance,
+    /// request fee paid upfront into the treasury isn't refunded.
+    /// Anyone can evict the expired request from the queue, lamports are then returned
+    /// to the requester.
+    ///
+    /// Accounts:
+    /// [W] Randomness request - account.
+    /// [RS] Caller - randomness request owner or anyone evicting the request.
+    /// [W] Destination - account to receive lamports, must be the requester unless it signs.
+    /// [R] Clock sysvar.
+    /// [W] Request queue - `find_request_queue_address(randomness oracle)`.
+    /// [W] Subscription - required if the request fee is reserved in subscription.
+    CancelRandomnessRequest,
+}
+
+/// Maximum size of a serialized transaction.
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// Maximum number of randomness oracles packed into one 'UpdateRandomnessOracles' instruction,
+/// keeps batches of history keeping oracles within the compute budget.
+pub const MAX_BATCH_UPDATES: usize = 8;
+
+/// Randomness oracle update packed into 'UpdateRandomnessOracles' instruction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RandomnessOracleUpdate {
+    /// Randomness oracle.
+    pub randomness_oracle: Pubkey,
+    /// Randomness oracle history, if history is kept.
+    pub history: Option<Pubkey>,
+    /// Random value.
+    pub value: [u8; 32],
 }
 
 /// Creates 'InitRandomnessOracle' instruction.
@@ -32,16 +420,31 @@ pub fn init_randomness_oracle(
     program_id: &Pubkey,
     randomness_oracle: &Pubkey,
     authority: &Pubkey,
+    min_update_interval_slots: u64,
+    metadata: RandomnessOracleMetadata,
+    bond: u64,
+    update_mode: UpdateMode,
 ) -> Instruction {
+    let (bond_vault, _) = find_bond_vault_address(program_id, randomness_oracle);
+    let (registry, _) = find_registry_address(program_id, authority);
     let accounts = vec![
         AccountMeta::new(*randomness_oracle, false),
-        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*authority, true),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(bond_vault, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(registry, false),
     ];
 
     Instruction::new_with_borsh(
         *program_id,
-        &RandomnessOracleInstruction::InitRandomnessOracle,
+        &RandomnessOracleInstruction::InitRandomnessOracle {
+            min_update_interval_slots,
+            metadata,
+            bond,
+            update_mode,
+        },
         accounts,
     )
 }
@@ -51,13 +454,17 @@ pub fn update_randomness_oracle(
     program_id: &Pubkey,
     randomness_oracle: &Pubkey,
     authority: &Pubkey,
+    history: Option<&Pubkey>,
     value: [u8; 32],
 ) -> Instruction {
-    let accounts = vec![
+    let mut accounts = vec![
         AccountMeta::new(*randomness_oracle, false),
         AccountMeta::new_readonly(*authority, true),
         AccountMeta::new_readonly(sysvar::clock::id(), false),
     ];
+    if let Some(history) = history {
+        accounts.push(AccountMeta::new(*history, false));
+    }
 
     Instruction::new_with_borsh(
         *program_id,
@@ -65,3 +472,755 @@ pub fn update_randomness_oracle(
         accounts,
     )
 }
+
+/// Creates 'CommitRandomness' instruction.
+pub fn commit_randomness(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    hash: [u8; 32],
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::CommitRandomness { hash },
+        accounts,
+    )
+}
+
+/// Creates 'RevealRandomness' instruction.
+pub fn reveal_randomness(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    history: Option<&Pubkey>,
+    seed: [u8; 32],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    if let Some(history) = history {
+        accounts.push(AccountMeta::new(*history, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::RevealRandomness { seed },
+        accounts,
+    )
+}
+
+/// Creates 'MixRandomnessOracle' instruction.
+pub fn mix_randomness_oracle(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    history: Option<&Pubkey>,
+    value: [u8; 32],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+    ];
+    if let Some(history) = history {
+        accounts.push(AccountMeta::new(*history, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::MixRandomnessOracle { value },
+        accounts,
+    )
+}
+
+/// Creates 'UpdateRandomnessOracleWithSignature' instruction.
+///
+/// Must be preceded by `ed25519::new_ed25519_instruction` verifying the same signature.
+pub fn update_randomness_oracle_with_signature(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    history: Option<&Pubkey>,
+    signature: [u8; 64],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+    ];
+    if let Some(history) = history {
+        accounts.push(AccountMeta::new(*history, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::UpdateRandomnessOracleWithSignature { signature },
+        accounts,
+    )
+}
+
+/// Creates 'ProposeAuthority' instruction.
+pub fn propose_authority(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::ProposeAuthority {
+            new_authority: *new_authority,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'AcceptAuthority' instruction.
+pub fn accept_authority(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    new_authority: &Pubkey,
+) -> Instruction {
+    let (registry, _) = find_registry_address(program_id, authority);
+    let (new_registry, _) = find_registry_address(program_id, new_authority);
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new(*new_authority, true),
+        AccountMeta::new(registry, false),
+        AccountMeta::new(new_registry, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::AcceptAuthority,
+        accounts,
+    )
+}
+
+/// Creates 'CloseRandomnessOracle' instruction.
+pub fn close_randomness_oracle(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    destination: &Pubkey,
+    history: Option<&Pubkey>,
+) -> Instruction {
+    let (bond_vault, _) = find_bond_vault_address(program_id, randomness_oracle);
+    let (registry, _) = find_registry_address(program_id, authority);
+    let (treasury, _) = find_treasury_address(program_id, randomness_oracle);
+    let (request_queue, _) = find_request_queue_address(program_id, randomness_oracle);
+    let mut accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new(bond_vault, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(registry, false),
+        AccountMeta::new(treasury, false),
+        AccountMeta::new(request_queue, false),
+    ];
+    if let Some(history) = history {
+        accounts.push(AccountMeta::new(*history, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::CloseRandomnessOracle,
+        accounts,
+    )
+}
+
+/// Creates 'InitRandomnessOracleHistory' instruction.
+pub fn init_randomness_oracle_history(
+    program_id: &Pubkey,
+    history: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*history, false),
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::InitRandomnessOracleHistory,
+        accounts,
+    )
+}
+
+/// Creates 'SetMinUpdateInterval' instruction.
+pub fn set_min_update_interval(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    min_update_interval_slots: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::SetMinUpdateInterval {
+            min_update_interval_slots,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'CreateRandomnessOracle' instruction.
+pub fn create_randomness_oracle(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    metadata: RandomnessOracleMetadata,
+    min_update_interval_slots: u64,
+    bond: u64,
+    update_mode: UpdateMode,
+) -> Instruction {
+    let (randomness_oracle, _) =
+        find_randomness_oracle_address(program_id, authority, &metadata.name);
+    let (bond_vault, _) = find_bond_vault_address(program_id, &randomness_oracle);
+    let (registry, _) = find_registry_address(program_id, authority);
+
+    let accounts = vec![
+        AccountMeta::new(randomness_oracle, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(bond_vault, false),
+        AccountMeta::new(registry, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::CreateRandomnessOracle {
+            metadata,
+            min_update_interval_slots,
+            bond,
+            update_mode,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'MigrateRandomnessOracle' instruction.
+pub fn migrate_randomness_oracle(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    new_randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let (registry, _) = find_registry_address(program_id, authority);
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new(*new_randomness_oracle, false),
+        AccountMeta::new(*authority, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(registry, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::MigrateRandomnessOracle,
+        accounts,
+    )
+}
+
+/// Creates 'UpdateMetadata' instruction.
+pub fn update_metadata(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    metadata: RandomnessOracleMetadata,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::UpdateMetadata { metadata },
+        accounts,
+    )
+}
+
+/// Creates 'RequestRandomness' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn request_randomness(
+    program_id: &Pubkey,
+    randomness_request: &Pubkey,
+    randomness_oracle: &Pubkey,
+    requester: &Pubkey,
+    subscription: Option<&Pubkey>,
+    seed: [u8; 32],
+    callback_program: Option<Pubkey>,
+    callback_accounts: Vec<CallbackAccount>,
+    callback_data: Vec<u8>,
+) -> Instruction {
+    let (treasury, _) = find_treasury_address(program_id, randomness_oracle);
+    let (request_queue, _) = find_request_queue_address(program_id, randomness_oracle);
+    let mut accounts = vec![
+        AccountMeta::new(*randomness_request, false),
+        AccountMeta::new_readonly(*randomness_oracle, false),
+        AccountMeta::new(*requester, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new(treasury, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new(request_queue, false),
+    ];
+
+    if let Some(subscription) = subscription {
+        accounts.push(AccountMeta::new(*subscription, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::RequestRandomness {
+            seed,
+            callback_program,
+            callback_accounts,
+            callback_data,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'FulfillRandomness' instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn fulfill_randomness(
+    program_id: &Pubkey,
+    randomness_request: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    subscription: Option<&Pubkey>,
+    callback_program: Option<&Pubkey>,
+    callback_accounts: &[CallbackAccount],
+) -> Instruction {
+    let (request_queue, _) = find_request_queue_address(program_id, randomness_oracle);
+    let mut accounts = vec![
+        AccountMeta::new(*randomness_request, false),
+        AccountMeta::new_readonly(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(sysvar::slot_hashes::id(), false),
+        AccountMeta::new(request_queue, false),
+    ];
+
+    if let Some(subscription) = subscription {
+        let (treasury, _) = find_treasury_address(program_id, randomness_oracle);
+        accounts.push(AccountMeta::new(*subscription, false));
+        accounts.push(AccountMeta::new(treasury, false));
+    }
+
+    if let Some(callback_program) = callback_program {
+        accounts.push(AccountMeta::new_readonly(*callback_program, false));
+        accounts.extend(callback_accounts.iter().map(|account| AccountMeta {
+            pubkey: account.pubkey,
+            is_signer: false,
+            is_writable: account.is_writable,
+        }));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::FulfillRandomness,
+        accounts,
+    )
+}
+
+/// Creates 'SetRequestFee' instruction.
+pub fn set_request_fee(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    request_fee: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::SetRequestFee { request_fee },
+        accounts,
+    )
+}
+
+/// Creates 'WithdrawFees' instruction.
+pub fn withdraw_fees(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    let (treasury, _) = find_treasury_address(program_id, randomness_oracle);
+    let accounts = vec![
+        AccountMeta::new_readonly(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new(treasury, false),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::WithdrawFees,
+        accounts,
+    )
+}
+
+/// Creates 'CreateSubscription' instruction.
+pub fn create_subscription(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    owner: &Pubkey,
+) -> Instruction {
+    let (subscription, _) = find_subscription_address(program_id, randomness_oracle, owner);
+    let accounts = vec![
+        AccountMeta::new(subscription, false),
+        AccountMeta::new_readonly(*randomness_oracle, false),
+        AccountMeta::new(*owner, true),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::CreateSubscription,
+        accounts,
+    )
+}
+
+/// Creates 'FundSubscription' instruction.
+pub fn fund_subscription(
+    program_id: &Pubkey,
+    subscription: &Pubkey,
+    funder: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*subscription, false),
+        AccountMeta::new(*funder, true),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::FundSubscription { amount },
+        accounts,
+    )
+}
+
+/// Creates 'CancelSubscription' instruction.
+pub fn cancel_subscription(
+    program_id: &Pubkey,
+    subscription: &Pubkey,
+    owner: &Pubkey,
+    destination: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*subscription, false),
+        AccountMeta::new_readonly(*owner, true),
+        AccountMeta::new(*destination, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::CancelSubscription,
+        accounts,
+    )
+}
+
+/// Creates 'SlashOperator' instruction.
+pub fn slash_operator(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    caller: &Pubkey,
+) -> Instruction {
+    let (bond_vault, _) = find_bond_vault_address(program_id, randomness_oracle);
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new(bond_vault, false),
+        AccountMeta::new(*caller, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::SlashOperator,
+        accounts,
+    )
+}
+
+/// Creates 'PauseRandomnessOracle' instruction.
+pub fn pause_randomness_oracle(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::PauseRandomnessOracle,
+        accounts,
+    )
+}
+
+/// Creates 'ResumeRandomnessOracle' instruction.
+pub fn resume_randomness_oracle(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    authority: &Pubkey,
+) -> Instruction {
+    let accounts = vec![
+        AccountMeta::new(*randomness_oracle, false),
+        AccountMeta::new_readonly(*authority, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::ResumeRandomnessOracle,
+        accounts,
+    )
+}
+
+/// Creates 'InitProgramConfig' instruction.
+pub fn init_program_config(
+    program_id: &Pubkey,
+    upgrade_authority: &Pubkey,
+    admin: &Pubkey,
+) -> Instruction {
+    let (program_config, _) = find_program_config_address(program_id);
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    let accounts = vec![
+        AccountMeta::new(program_config, false),
+        AccountMeta::new(*upgrade_authority, true),
+        AccountMeta::new_readonly(program_data, false),
+        AccountMeta::new_readonly(sysvar::rent::id(), false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::InitProgramConfig { admin: *admin },
+        accounts,
+    )
+}
+
+/// Creates 'ApproveOracle' instruction.
+pub fn approve_oracle(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    randomness_oracle: &Pubkey,
+) -> Instruction {
+    let (program_config, _) = find_program_config_address(program_id);
+    let accounts = vec![
+        AccountMeta::new_readonly(program_config, false),
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(*randomness_oracle, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::ApproveOracle,
+        accounts,
+    )
+}
+
+/// Creates 'RevokeOracle' instruction.
+pub fn revoke_oracle(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    randomness_oracle: &Pubkey,
+) -> Instruction {
+    let (program_config, _) = find_program_config_address(program_id);
+    let accounts = vec![
+        AccountMeta::new_readonly(program_config, false),
+        AccountMeta::new_readonly(*admin, true),
+        AccountMeta::new(*randomness_oracle, false),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::RevokeOracle,
+        accounts,
+    )
+}
+
+/// Creates 'UpdateRandomnessOracles' instructions.
+///
+/// Updates are split into as many instructions as needed, so that each instruction has
+/// at most `MAX_BATCH_UPDATES` updates and fits in a transaction signed and paid by the authority.
+pub fn update_randomness_oracles(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    updates: &[RandomnessOracleUpdate],
+) -> Vec<Instruction> {
+    let mut instructions = vec![];
+    let mut start = 0;
+    while start < updates.len() {
+        let mut end = start + 1;
+        while end < updates.len() && end - start < MAX_BATCH_UPDATES {
+            let instruction =
+                pack_randomness_oracle_updates(program_id, authority, &updates[start..=end]);
+            if transaction_size(&instruction, authority) > MAX_TRANSACTION_SIZE {
+                break;
+            }
+            end += 1;
+        }
+
+        instructions.push(pack_randomness_oracle_updates(
+            program_id,
+            authority,
+            &updates[start..end],
+        ));
+        start = end;
+    }
+
+    instructions
+}
+
+fn pack_randomness_oracle_updates(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    updates: &[RandomnessOracleUpdate],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*authority, true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    for update in updates {
+        accounts.push(AccountMeta::new(update.randomness_oracle, false));
+        if let Some(history) = update.history {
+            accounts.push(AccountMeta::new(history, false));
+        }
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::UpdateRandomnessOracles {
+            values: updates.iter().map(|update| update.value).collect(),
+        },
+        accounts,
+    )
+}
+
+fn transaction_size(instruction: &Instruction, payer: &Pubkey) -> usize {
+    let message = Message::new(std::slice::from_ref(instruction), Some(payer));
+    let signatures = message.header.num_required_signatures as usize;
+
+    // Compact length prefix of 64-byte signatures followed by the message
+    1 + signatures * 64 + message.serialize().len()
+}
+
+/// Creates 'ProposeAdmin' instruction.
+pub fn propose_admin(program_id: &Pubkey, admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    let (program_config, _) = find_program_config_address(program_id);
+    let accounts = vec![
+        AccountMeta::new(program_config, false),
+        AccountMeta::new_readonly(*admin, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::ProposeAdmin {
+            new_admin: *new_admin,
+        },
+        accounts,
+    )
+}
+
+/// Creates 'AcceptAdmin' instruction.
+pub fn accept_admin(program_id: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    let (program_config, _) = find_program_config_address(program_id);
+    let accounts = vec![
+        AccountMeta::new(program_config, false),
+        AccountMeta::new_readonly(*new_admin, true),
+    ];
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::AcceptAdmin,
+        accounts,
+    )
+}
+
+/// Creates 'CancelRandomnessRequest' instruction.
+pub fn cancel_randomness_request(
+    program_id: &Pubkey,
+    randomness_request: &Pubkey,
+    randomness_oracle: &Pubkey,
+    caller: &Pubkey,
+    destination: &Pubkey,
+    subscription: Option<&Pubkey>,
+) -> Instruction {
+    let (request_queue, _) = find_request_queue_address(program_id, randomness_oracle);
+    let mut accounts = vec![
+        AccountMeta::new(*randomness_request, false),
+        AccountMeta::new_readonly(*caller, true),
+        AccountMeta::new(*destination, false),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+        AccountMeta::new(request_queue, false),
+    ];
+    if let Some(subscription) = subscription {
+        accounts.push(AccountMeta::new(*subscription, false));
+    }
+
+    Instruction::new_with_borsh(
+        *program_id,
+        &RandomnessOracleInstruction::CancelRandomnessRequest,
+        accounts,
+    )
+}
+
+/// Creates callback instruction invoked by 'FulfillRandomness'.
+pub fn randomness_callback(
+    callback_program: &Pubkey,
+    randomness_request: &Pubkey,
+    callback_accounts: &[CallbackAccount],
+    callback_data: &[u8],
+) -> Instruction {
+    let mut accounts = vec![AccountMeta::new_readonly(*randomness_request, false)];
+    accounts.extend(callback_accounts.iter().map(|account| AccountMeta {
+        pubkey: account.pubkey,
+        is_signer: false,
+        is_writable: account.is_writable,
+    }));
+
+    Instruction::new_with_bytes(*callback_program, callback_data, accounts)
+}
@@ -1,3 +1,6 @@
+pub mod ed25519;
+pub mod error;
+pub mod event;
 pub mod instruction;
 pub mod processor;
 pub mod state;
@@ -8,16 +11,364 @@ pub mod entrypoint;
 
 // Export current sdk types for downstream users building with a different sdk version.
 pub use solana_program;
+
+use error::RandomnessOracleError;
 use solana_program::{
-    account_info::AccountInfo, clock::Slot, program_error::ProgramError, program_pack::Pack,
+    account_info::AccountInfo,
+    clock::{Clock, Slot, UnixTimestamp},
+    program_error::ProgramError,
+    program_pack::Pack,
+    pubkey::{Pubkey, MAX_SEED_LEN},
+    sysvar::{
+        self,
+        instructions::{load_current_index, load_instruction_at},
+    },
 };
-use state::RandomnessOracle;
+use state::{AccountType, RandomnessOracle, RandomnessOracleHistory, RandomnessRequest};
 
 solana_program::declare_id!("FfYvEMJip3kLpSJKfyLRXhp8f8yuSSaLxtjzaFecLT9s");
 
-pub fn read_value(randomness_oracle_info: &AccountInfo) -> Result<([u8; 32], Slot), ProgramError> {
-    let RandomnessOracle { value, slot, .. } =
-        RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+/// Seed prefix of randomness oracle program derived addresses.
+pub const RANDOMNESS_ORACLE_SEED: &[u8] = b"randomness_oracle";
+
+/// Seed of program config address.
+pub const PROGRAM_CONFIG_SEED: &[u8] = b"config";
+
+/// Find program config address.
+pub fn find_program_config_address(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROGRAM_CONFIG_SEED], program_id)
+}
+
+/// Seed prefix of randomness oracle registry address.
+pub const REGISTRY_SEED: &[u8] = b"registry";
+
+/// Find address of the registry listing randomness oracles of the authority.
+pub fn find_registry_address(program_id: &Pubkey, authority: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_SEED, authority.as_ref()], program_id)
+}
+
+/// Seed prefix of randomness oracle treasury address.
+pub const TREASURY_SEED: &[u8] = b"treasury";
+
+/// Find treasury address collecting randomness oracle fees.
+pub fn find_treasury_address(program_id: &Pubkey, randomness_oracle: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TREASURY_SEED, randomness_oracle.as_ref()], program_id)
+}
+
+/// Seed prefix of randomness oracle bond vault address.
+pub const BOND_VAULT_SEED: &[u8] = b"bond";
+
+/// Find vault address holding randomness oracle operator bond.
+pub fn find_bond_vault_address(program_id: &Pubkey, randomness_oracle: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BOND_VAULT_SEED, randomness_oracle.as_ref()], program_id)
+}
+
+/// Seed prefix of randomness request queue address.
+pub const REQUEST_QUEUE_SEED: &[u8] = b"requests";
+
+/// Find address of the queue listing pending randomness requests of the randomness oracle.
+pub fn find_request_queue_address(program_id: &Pubkey, randomness_oracle: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REQUEST_QUEUE_SEED, randomness_oracle.as_ref()],
+        program_id,
+    )
+}
+
+/// Seed prefix of subscription address.
+pub const SUBSCRIPTION_SEED: &[u8] = b"subscription";
+
+/// Find subscription address of the owner to randomness oracle.
+pub fn find_subscription_address(
+    program_id: &Pubkey,
+    randomness_oracle: &Pubkey,
+    owner: &Pubkey,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[
+            SUBSCRIPTION_SEED,
+            randomness_oracle.as_ref(),
+            owner.as_ref(),
+        ],
+        program_id,
+    )
+}
+
+/// Find randomness oracle program derived address for authority and name.
+///
+/// Name must be at most `MAX_SEED_LEN` bytes long.
+pub fn find_randomness_oracle_address(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    name: &str,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[RANDOMNESS_ORACLE_SEED, authority.as_ref(), name.as_bytes()],
+        program_id,
+    )
+}
+
+/// Check randomness oracle name can be used as a seed.
+pub fn is_valid_randomness_oracle_name(name: &str) -> bool {
+    name.len() <= MAX_SEED_LEN
+}
+
+/// Read randomness oracle value, slot and round of its last update.
+pub fn read_value(
+    randomness_oracle_info: &AccountInfo,
+) -> Result<([u8; 32], Slot, u64), ProgramError> {
+    let data = randomness_oracle_info.data.borrow();
+    if !RandomnessOracle::is_supported_len(data.len()) {
+        return Err(RandomnessOracleError::WrongAccountSize.into());
+    }
+
+    let RandomnessOracle {
+        value,
+        slot,
+        round,
+        paused,
+        ..
+    } = RandomnessOracle::unpack(&data)?;
+
+    if paused {
+        return Err(RandomnessOracleError::OraclePaused.into());
+    }
+
+    Ok((value, slot, round))
+}
+
+/// Read randomness oracle value, slot and round of its last update.
+///
+/// Unlike `read_value` checks that account is an initialized randomness oracle
+/// owned by this program and, if provided, managed by the expected authority.
+pub fn read_value_checked(
+    randomness_oracle_info: &AccountInfo,
+    expected_authority: Option<&Pubkey>,
+) -> Result<([u8; 32], Slot, u64), ProgramError> {
+    let RandomnessOracle {
+        value, slot, round, ..
+    } = load_randomness_oracle(randomness_oracle_info, expected_authority)?;
+
+    Ok((value, slot, round))
+}
+
+/// Read randomness oracle value, slot and round of its last update,
+/// failing if the value is older than `max_age_slots`.
+pub fn read_value_fresh(
+    randomness_oracle_info: &AccountInfo,
+    clock: &Clock,
+    max_age_slots: u64,
+) -> Result<([u8; 32], Slot, u64), ProgramError> {
+    let RandomnessOracle {
+        value, slot, round, ..
+    } = load_randomness_oracle(randomness_oracle_info, None)?;
+
+    if clock.slot.saturating_sub(slot) > max_age_slots {
+        return Err(RandomnessOracleError::StaleValue.into());
+    }
+
+    Ok((value, slot, round))
+}
+
+/// Read randomness oracle value, slot and round of its last update,
+/// failing if the value is older than `max_age_seconds`.
+pub fn read_value_fresh_by_time(
+    randomness_oracle_info: &AccountInfo,
+    clock: &Clock,
+    max_age_seconds: UnixTimestamp,
+) -> Result<([u8; 32], Slot, u64), ProgramError> {
+    let RandomnessOracle {
+        value,
+        slot,
+        round,
+        unix_timestamp,
+        ..
+    } = load_randomness_oracle(randomness_oracle_info, None)?;
+
+    if clock.unix_timestamp.saturating_sub(unix_timestamp) > max_age_seconds {
+        return Err(RandomnessOracleError::StaleValue.into());
+    }
+
+    Ok((value, slot, round))
+}
+
+/// Read randomness oracle value, slot and round of its last update,
+/// failing if the value was written in the current slot.
+pub fn read_value_settled(
+    randomness_oracle_info: &AccountInfo,
+    clock: &Clock,
+) -> Result<([u8; 32], Slot, u64), ProgramError> {
+    let RandomnessOracle {
+        value, slot, round, ..
+    } = load_randomness_oracle(randomness_oracle_info, None)?;
+
+    if slot >= clock.slot {
+        return Err(RandomnessOracleError::ValueNotSettled.into());
+    }
+
+    Ok((value, slot, round))
+}
+
+/// Read randomness oracle value, slot and round of its last update,
+/// failing with `OracleNotApproved` if the oracle is not approved by the program config admin.
+pub fn read_value_approved(
+    randomness_oracle_info: &AccountInfo,
+) -> Result<([u8; 32], Slot, u64), ProgramError> {
+    let RandomnessOracle {
+        value,
+        slot,
+        round,
+        approved,
+        ..
+    } = load_randomness_oracle(randomness_oracle_info, None)?;
+
+    if !approved {
+        return Err(RandomnessOracleError::OracleNotApproved.into());
+    }
+
+    Ok((value, slot, round))
+}
+
+/// Read randomness oracle value, slot and round of its last update,
+/// failing if the oracle is not approved or the value is older than `max_age_slots`.
+pub fn read_value_fresh_approved(
+    randomness_oracle_info: &AccountInfo,
+    clock: &Clock,
+    max_age_slots: u64,
+) -> Result<([u8; 32], Slot, u64), ProgramError> {
+    let (value, slot, round) = read_value_approved(randomness_oracle_info)?;
+
+    if clock.slot.saturating_sub(slot) > max_age_slots {
+        return Err(RandomnessOracleError::StaleValue.into());
+    }
+
+    Ok((value, slot, round))
+}
+
+/// Read the first randomness oracle value published at or after `slot`
+/// together with the slot and round it was published at.
+///
+/// Fails with `HistoryExpired` if the value could have been overwritten
+/// in history and with `ValueNotPublished` if there is no such value yet.
+pub fn read_value_at_slot(
+    history_info: &AccountInfo,
+    randomness_oracle: &Pubkey,
+    slot: Slot,
+) -> Result<([u8; 32], Slot, u64), ProgramError> {
+    if *history_info.owner != id() {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = history_info.data.borrow();
+    if data.len() != RandomnessOracleHistory::LEN {
+        return Err(RandomnessOracleError::WrongAccountSize.into());
+    }
+
+    let history = RandomnessOracleHistory::unpack_unchecked(&data)?;
+    if history.account_type != AccountType::RandomnessOracleHistory {
+        return Err(RandomnessOracleError::InvalidAccountType.into());
+    }
+
+    if history.randomness_oracle != *randomness_oracle {
+        return Err(RandomnessOracleError::InvalidHistory.into());
+    }
+
+    let entry = history.find_at_slot(slot)?;
+
+    Ok((entry.value, entry.slot, entry.round))
+}
+
+/// Read fulfilled randomness request value and slot it was fulfilled at.
+///
+/// Checks that request was made by the expected requester, callback programs
+/// should pass the account they requested randomness with.
+pub fn read_request_value(
+    randomness_request_info: &AccountInfo,
+    expected_requester: &Pubkey,
+) -> Result<([u8; 32], Slot), ProgramError> {
+    if *randomness_request_info.owner != id() {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = randomness_request_info.data.borrow();
+    if data.len() != RandomnessRequest::LEN {
+        return Err(RandomnessOracleError::WrongAccountSize.into());
+    }
+
+    let randomness_request = RandomnessRequest::unpack_unchecked(&data)?;
+    if randomness_request.account_type != AccountType::RandomnessRequest {
+        return Err(RandomnessOracleError::InvalidAccountType.into());
+    }
+
+    if randomness_request.requester != *expected_requester {
+        return Err(RandomnessOracleError::InvalidRandomnessRequest.into());
+    }
+
+    if !randomness_request.fulfilled {
+        return Err(RandomnessOracleError::RequestNotFulfilled.into());
+    }
+
+    Ok((randomness_request.value, randomness_request.fulfilled_slot))
+}
+
+/// Assert that none of the preceding instructions of the transaction
+/// takes randomness oracle as a writable account.
+///
+/// Any update, including one made through CPI, requires randomness oracle
+/// to be writable in the top level instruction.
+pub fn assert_no_prior_update(
+    randomness_oracle_info: &AccountInfo,
+    instructions_info: &AccountInfo,
+) -> Result<(), ProgramError> {
+    if !sysvar::instructions::check_id(instructions_info.key) {
+        return Err(RandomnessOracleError::InvalidSysvar.into());
+    }
+
+    let data = instructions_info.data.borrow();
+    let current_index = load_current_index(&data);
+
+    for index in 0..current_index {
+        let instruction = load_instruction_at(index as usize, &data)
+            .map_err(|_| ProgramError::InvalidAccountData)?;
+
+        if instruction
+            .accounts
+            .iter()
+            .any(|meta| meta.is_writable && meta.pubkey == *randomness_oracle_info.key)
+        {
+            return Err(RandomnessOracleError::UpdatedInTransaction.into());
+        }
+    }
+
+    Ok(())
+}
+
+fn load_randomness_oracle(
+    randomness_oracle_info: &AccountInfo,
+    expected_authority: Option<&Pubkey>,
+) -> Result<RandomnessOracle, ProgramError> {
+    if *randomness_oracle_info.owner != id() {
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    let data = randomness_oracle_info.data.borrow();
+    if !RandomnessOracle::is_supported_len(data.len()) {
+        return Err(RandomnessOracleError::WrongAccountSize.into());
+    }
+
+    let randomness_oracle = RandomnessOracle::unpack_unchecked(&data)?;
+    if randomness_oracle.account_type != AccountType::RandomnessOracle {
+        return Err(RandomnessOracleError::InvalidAccountType.into());
+    }
+
+    if let Some(expected_authority) = expected_authority {
+        if randomness_oracle.authority != *expected_authority {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+    }
+
+    if randomness_oracle.paused {
+        return Err(RandomnessOracleError::OraclePaused.into());
+    }
 
-    Ok((value, slot))
+    Ok(randomness_oracle)
 }
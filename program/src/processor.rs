@@ -1,18 +1,39 @@
 //! Program state processor.
 
 use crate::{
-    instruction::RandomnessOracleInstruction,
-    state::{InitRandomnessOracleParams, RandomnessOracle},
+    ed25519,
+    error::RandomnessOracleError,
+    event::{RandomnessOracleEvent, RandomnessOracleEventKind},
+    find_bond_vault_address, find_program_config_address, find_randomness_oracle_address,
+    find_registry_address, find_request_queue_address, find_subscription_address,
+    find_treasury_address,
+    instruction::{randomness_callback, RandomnessOracleInstruction, MAX_BATCH_UPDATES},
+    is_valid_randomness_oracle_name,
+    state::{
+        AccountType, CallbackAccount, InitProgramConfigParams, InitRandomnessOracleHistoryParams,
+        InitRandomnessOracleParams, InitRandomnessOracleRegistryParams,
+        InitRandomnessRequestParams, InitRandomnessRequestQueueParams, InitSubscriptionParams,
+        ProgramConfig, RandomnessOracle, RandomnessOracleHistory, RandomnessOracleMetadata,
+        RandomnessOracleRegistry, RandomnessOracleV1, RandomnessRequest, RandomnessRequestQueue,
+        Subscription, UpdateMode, MAX_CALLBACK_ACCOUNTS, MAX_CALLBACK_DATA_LEN, MIN_BOND_LAMPORTS,
+        SLASH_DIVISOR,
+    },
     utils::*,
+    BOND_VAULT_SEED, PROGRAM_CONFIG_SEED, RANDOMNESS_ORACLE_SEED, REGISTRY_SEED,
+    REQUEST_QUEUE_SEED, SUBSCRIPTION_SEED, TREASURY_SEED,
 };
 use borsh::BorshDeserialize;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
     entrypoint::ProgramResult,
+    hash::{hash, hashv},
     msg,
+    program::{invoke, invoke_signed},
     program_error::ProgramError,
-    program_pack::Pack,
+    program_pack::{IsInitialized, Pack},
     pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
     sysvar::Sysvar,
 };
 
@@ -20,12 +41,24 @@ use solana_program::{
 pub struct Processor {}
 impl Processor {
     /// Process `InitRandomnessOracle` instruction.
-    pub fn init_randomness_oracle(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    pub fn init_randomness_oracle(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        min_update_interval_slots: u64,
+        metadata: RandomnessOracleMetadata,
+        bond: u64,
+        update_mode: UpdateMode,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let randomness_oracle_info = next_account_info(account_info_iter)?;
         let authority_info = next_account_info(account_info_iter)?;
         let clock_info = next_account_info(account_info_iter)?;
         let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let bond_vault_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
 
         // Check signer
         assert_signer(authority_info)?;
@@ -33,16 +66,62 @@ impl Processor {
         // Check random oracle owner
         assert_owned_by(randomness_oracle_info, program_id)?;
 
+        // Check metadata
+        if !metadata.is_valid() {
+            return Err(RandomnessOracleError::InvalidMetadata.into());
+        }
+
+        // Check random oracle account
+        assert_data_len(randomness_oracle_info, RandomnessOracle::LEN)?;
+        assert_rent_exempt(rent, randomness_oracle_info)?;
+
         // Get state
         let mut randomness_oracle =
             RandomnessOracle::unpack_unchecked(&randomness_oracle_info.data.borrow())?;
+        if randomness_oracle.is_closed() {
+            return Err(RandomnessOracleError::OracleClosed.into());
+        }
+        assert_uninitialized(&randomness_oracle)?;
 
         // Initialize
         randomness_oracle.init(InitRandomnessOracleParams {
             authority: *authority_info.key,
             slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+            min_update_interval_slots,
+            metadata,
+            update_mode,
         });
 
+        // Deposit bond
+        Self::deposit_bond(
+            program_id,
+            randomness_oracle_info,
+            authority_info,
+            bond_vault_info,
+            system_program_info,
+            rent,
+            bond,
+        )?;
+
+        // Register
+        Self::register(
+            program_id,
+            randomness_oracle_info.key,
+            authority_info,
+            registry_info,
+            system_program_info,
+            rent,
+        )?;
+
+        // Emit event
+        RandomnessOracleEvent::new(
+            RandomnessOracleEventKind::Initialized,
+            randomness_oracle_info.key,
+            &randomness_oracle,
+        )
+        .emit();
+
         // Save state
         RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
 
@@ -64,6 +143,221 @@ impl Processor {
         // Check signer
         assert_signer(authority_info)?;
 
+        Self::apply_update(
+            program_id,
+            randomness_oracle_info,
+            authority_info,
+            &clock,
+            account_info_iter,
+            UpdateMode::Direct,
+            |randomness_oracle| {
+                randomness_oracle.update(value, clock.slot, clock.unix_timestamp);
+                Ok(())
+            },
+        )
+    }
+
+    /// Process `CommitRandomness` instruction.
+    pub fn commit_randomness(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        hash: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Check random oracle is not paused
+        if randomness_oracle.paused {
+            return Err(RandomnessOracleError::OraclePaused.into());
+        }
+
+        // Check random oracle update mode
+        if randomness_oracle.update_mode == UpdateMode::Direct {
+            return Err(RandomnessOracleError::InvalidUpdateMode.into());
+        }
+
+        // Previous commitment should be revealed first
+        if randomness_oracle.has_commitment() {
+            return Err(RandomnessOracleError::CommitmentAlreadyPending.into());
+        }
+
+        // Empty hash is reserved for "no commitment"
+        if hash == [0u8; 32] {
+            return Err(RandomnessOracleError::InvalidCommitment.into());
+        }
+
+        // Check minimum update interval, so that the reveal can't be held back by it
+        // past the reveal deadline
+        if !randomness_oracle.can_update(clock.slot) {
+            return Err(RandomnessOracleError::UpdateTooEarly.into());
+        }
+
+        // Commit
+        randomness_oracle.commit(hash, clock.slot);
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `RevealRandomness` instruction.
+    pub fn reveal_randomness(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seed: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        Self::apply_update(
+            program_id,
+            randomness_oracle_info,
+            authority_info,
+            &clock,
+            account_info_iter,
+            UpdateMode::CommitReveal,
+            |randomness_oracle| {
+                // Check seed against commitment
+                randomness_oracle.check_reveal(hash(&seed).to_bytes(), clock.slot)?;
+
+                randomness_oracle.reveal(seed, clock.slot, clock.unix_timestamp);
+                Ok(())
+            },
+        )
+    }
+
+    /// Process `MixRandomnessOracle` instruction.
+    pub fn mix_randomness_oracle(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        value: [u8; 32],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+        let slot_hashes_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        Self::apply_update(
+            program_id,
+            randomness_oracle_info,
+            authority_info,
+            &clock,
+            account_info_iter,
+            UpdateMode::Mix,
+            |randomness_oracle| {
+                // Check value against commitment
+                randomness_oracle.check_reveal(hash(&value).to_bytes(), clock.slot)?;
+
+                // Commitment slot hash wasn't known when the value was committed
+                let slot_hash = get_slot_hash(slot_hashes_info, randomness_oracle.commit_slot)?;
+
+                randomness_oracle.mix(
+                    value,
+                    slot_hash.to_bytes(),
+                    clock.slot,
+                    clock.unix_timestamp,
+                );
+                Ok(())
+            },
+        )
+    }
+
+    /// Process `UpdateRandomnessOracleWithSignature` instruction.
+    pub fn update_randomness_oracle_with_signature(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signature: [u8; 64],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+        let instructions_info = next_account_info(account_info_iter)?;
+        let slot_hashes_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        Self::apply_update(
+            program_id,
+            randomness_oracle_info,
+            authority_info,
+            &clock,
+            account_info_iter,
+            UpdateMode::Signature,
+            |randomness_oracle| {
+                // Check signature against commitment
+                randomness_oracle.check_reveal(hash(&signature).to_bytes(), clock.slot)?;
+
+                // Check signature through the preceding Ed25519 program instruction
+                let (ed25519_instruction, ed25519_instruction_index) =
+                    load_previous_instruction(instructions_info)?;
+                ed25519::verify_ed25519_instruction(
+                    &ed25519_instruction,
+                    ed25519_instruction_index,
+                    authority_info.key,
+                    &signature,
+                    &randomness_oracle.signature_message(randomness_oracle_info.key),
+                )?;
+
+                // Commitment slot hash wasn't known when the signature was committed
+                let slot_hash = get_slot_hash(slot_hashes_info, randomness_oracle.commit_slot)?;
+
+                randomness_oracle.reveal_signature(
+                    &signature,
+                    slot_hash.to_bytes(),
+                    clock.slot,
+                    clock.unix_timestamp,
+                );
+                Ok(())
+            },
+        )
+    }
+
+    /// Process `ProposeAuthority` instruction.
+    pub fn propose_authority(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_authority: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
         // Check random oracle owner
         assert_owned_by(randomness_oracle_info, program_id)?;
 
@@ -73,34 +367,1905 @@ impl Processor {
 
         // Check random oracle authority
         if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Propose
+        randomness_oracle.propose_authority(new_authority);
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `AcceptAuthority` instruction.
+    pub fn accept_authority(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let new_authority_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
+        let new_registry_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(new_authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check proposed authority
+        if !randomness_oracle.is_pending_authority(new_authority_info.key) {
+            return Err(RandomnessOracleError::InvalidPendingAuthority.into());
+        }
+
+        // Move to the registry of the new authority
+        Self::unregister(
+            program_id,
+            randomness_oracle_info.key,
+            &randomness_oracle.authority,
+            registry_info,
+        )?;
+        Self::register(
+            program_id,
+            randomness_oracle_info.key,
+            new_authority_info,
+            new_registry_info,
+            system_program_info,
+            rent,
+        )?;
+
+        // Accept
+        randomness_oracle.accept_authority();
+
+        // Emit event
+        RandomnessOracleEvent::new(
+            RandomnessOracleEventKind::AuthorityChanged,
+            randomness_oracle_info.key,
+            &randomness_oracle,
+        )
+        .emit();
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `CloseRandomnessOracle` instruction.
+    pub fn close_randomness_oracle(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let bond_vault_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
+        let treasury_info = next_account_info(account_info_iter)?;
+        let request_queue_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Lamports can't be returned to the closed account itself
+        if randomness_oracle_info.key == destination_info.key {
             return Err(ProgramError::InvalidArgument);
         }
 
-        // Update
-        randomness_oracle.update(value, clock.slot);
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Pending commitment keeps the bond locked until it is revealed or slashed
+        if randomness_oracle.has_commitment() {
+            return Err(RandomnessOracleError::CommitmentAlreadyPending.into());
+        }
+
+        // Return bond
+        Self::withdraw_bond(
+            program_id,
+            randomness_oracle_info,
+            bond_vault_info,
+            destination_info,
+            system_program_info,
+            bond_vault_info.lamports(),
+        )?;
+
+        // Return collected fees
+        Self::withdraw_treasury(
+            program_id,
+            randomness_oracle_info,
+            treasury_info,
+            destination_info,
+            system_program_info,
+        )?;
+
+        // Unregister
+        Self::unregister(
+            program_id,
+            randomness_oracle_info.key,
+            authority_info.key,
+            registry_info,
+        )?;
+
+        // Emit event
+        RandomnessOracleEvent::new(
+            RandomnessOracleEventKind::Closed,
+            randomness_oracle_info.key,
+            &randomness_oracle,
+        )
+        .emit();
+
+        // Close history
+        Self::close_history(
+            program_id,
+            &randomness_oracle,
+            destination_info,
+            account_info_iter,
+        )?;
+
+        // Close request queue
+        Self::close_request_queue(
+            program_id,
+            randomness_oracle_info.key,
+            request_queue_info,
+            destination_info,
+        )?;
+
+        // Close, closed account type prevents re-initialization within the same transaction
+        randomness_oracle.close();
 
         // Save state
         RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
 
+        // Transfer all lamports
+        let lamports = randomness_oracle_info.lamports();
+        **destination_info.lamports.borrow_mut() = destination_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **randomness_oracle_info.lamports.borrow_mut() = 0;
+
         Ok(())
     }
 
-    /// Instruction processing router.
-    pub fn process_instruction(
+    /// Process `InitRandomnessOracleHistory` instruction.
+    pub fn init_randomness_oracle_history(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        input: &[u8],
     ) -> ProgramResult {
-        let instruction = RandomnessOracleInstruction::try_from_slice(input)?;
+        let account_info_iter = &mut accounts.iter();
+        let history_info = next_account_info(account_info_iter)?;
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
 
-        match instruction {
-            RandomnessOracleInstruction::InitRandomnessOracle => {
-                msg!("RandomnessOracleInstruction: InitRandomnessOracle");
-                Self::init_randomness_oracle(program_id, accounts)
-            }
-            RandomnessOracleInstruction::UpdateRandomnessOracle { value } => {
-                msg!("RandomnessOracleInstruction: UpdateRandomnessOracle");
-                Self::update_randomness_oracle(program_id, accounts, value)
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Check history account
+        assert_owned_by(history_info, program_id)?;
+        assert_data_len(history_info, RandomnessOracleHistory::LEN)?;
+        assert_rent_exempt(rent, history_info)?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+        let mut history = RandomnessOracleHistory::unpack_unchecked(&history_info.data.borrow())?;
+        assert_uninitialized(&history)?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Consumers rely on history being continuous, so it can't be replaced
+        if randomness_oracle.history_address().is_some() {
+            return Err(RandomnessOracleError::HistoryAlreadySet.into());
+        }
+
+        // Initialize
+        history.init(InitRandomnessOracleHistoryParams {
+            randomness_oracle: *randomness_oracle_info.key,
+        });
+        history.push(
+            randomness_oracle.slot,
+            randomness_oracle.round,
+            randomness_oracle.value,
+        );
+        randomness_oracle.history = *history_info.key;
+
+        // Save state
+        RandomnessOracleHistory::pack(history, *history_info.data.borrow_mut())?;
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `SetMinUpdateInterval` instruction.
+    pub fn set_min_update_interval(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        min_update_interval_slots: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Set
+        randomness_oracle.min_update_interval_slots = min_update_interval_slots;
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `CreateRandomnessOracle` instruction.
+    pub fn create_randomness_oracle(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        metadata: RandomnessOracleMetadata,
+        min_update_interval_slots: u64,
+        bond: u64,
+        update_mode: UpdateMode,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let bond_vault_info = next_account_info(account_info_iter)?;
+        let registry_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check metadata
+        if !metadata.is_valid() || !is_valid_randomness_oracle_name(&metadata.name) {
+            return Err(RandomnessOracleError::InvalidMetadata.into());
+        }
+
+        // Check random oracle address
+        let (randomness_oracle_key, bump_seed) =
+            find_randomness_oracle_address(program_id, authority_info.key, &metadata.name);
+        if randomness_oracle_key != *randomness_oracle_info.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        // Create random oracle account
+        create_program_derived_account(
+            authority_info,
+            randomness_oracle_info,
+            system_program_info,
+            rent,
+            RandomnessOracle::LEN,
+            program_id,
+            &[
+                RANDOMNESS_ORACLE_SEED,
+                authority_info.key.as_ref(),
+                metadata.name.as_bytes(),
+                &[bump_seed],
+            ],
+        )?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack_unchecked(&randomness_oracle_info.data.borrow())?;
+
+        // Initialize
+        randomness_oracle.init(InitRandomnessOracleParams {
+            authority: *authority_info.key,
+            slot: clock.slot,
+            unix_timestamp: clock.unix_timestamp,
+            min_update_interval_slots,
+            metadata,
+            update_mode,
+        });
+        randomness_oracle.derived = true;
+
+        // Deposit bond
+        Self::deposit_bond(
+            program_id,
+            randomness_oracle_info,
+            authority_info,
+            bond_vault_info,
+            system_program_info,
+            rent,
+            bond,
+        )?;
+
+        // Register
+        Self::register(
+            program_id,
+            randomness_oracle_info.key,
+            authority_info,
+            registry_info,
+            system_program_info,
+            rent,
+        )?;
+
+        // Emit event
+        RandomnessOracleEvent::new(
+            RandomnessOracleEventKind::Initialized,
+            randomness_oracle_info.key,
+            &randomness_oracle,
+        )
+        .emit();
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `MigrateRandomnessOracle` instruction.
+    pub fn migrate_randomness_oracle(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let new_randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let registry_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+        assert_owned_by(new_randomness_oracle_info, program_id)?;
+
+        // Check random oracle accounts
+        assert_data_len(randomness_oracle_info, RandomnessOracleV1::LEN)?;
+        assert_data_len(new_randomness_oracle_info, RandomnessOracle::LEN)?;
+        assert_rent_exempt(rent, new_randomness_oracle_info)?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+        let new_randomness_oracle =
+            RandomnessOracle::unpack_unchecked(&new_randomness_oracle_info.data.borrow())?;
+        if new_randomness_oracle.is_closed() {
+            return Err(RandomnessOracleError::OracleClosed.into());
+        }
+        assert_uninitialized(&new_randomness_oracle)?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Migrate
+        randomness_oracle.migrate();
+
+        // Register the new account
+        Self::register(
+            program_id,
+            new_randomness_oracle_info.key,
+            authority_info,
+            registry_info,
+            system_program_info,
+            rent,
+        )?;
+
+        // Emit event
+        RandomnessOracleEvent::new(
+            RandomnessOracleEventKind::Initialized,
+            new_randomness_oracle_info.key,
+            &randomness_oracle,
+        )
+        .emit();
+
+        // Save state
+        RandomnessOracle::pack(
+            randomness_oracle,
+            *new_randomness_oracle_info.data.borrow_mut(),
+        )?;
+
+        // Close old account, closed account type prevents its re-initialization
+        RandomnessOracleV1 {
+            account_type: AccountType::ClosedRandomnessOracle,
+            ..Default::default()
+        }
+        .pack_into_slice(*randomness_oracle_info.data.borrow_mut());
+
+        // Transfer all lamports
+        let lamports = randomness_oracle_info.lamports();
+        **authority_info.lamports.borrow_mut() = authority_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **randomness_oracle_info.lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /// Process `UpdateMetadata` instruction.
+    pub fn update_metadata(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        metadata: RandomnessOracleMetadata,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Check metadata
+        if !metadata.is_valid() {
+            return Err(RandomnessOracleError::InvalidMetadata.into());
+        }
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Name is part of the derived random oracle address
+        if randomness_oracle.derived && randomness_oracle.metadata().name != metadata.name {
+            return Err(RandomnessOracleError::ImmutableName.into());
+        }
+
+        // Changed metadata has to be vetted again
+        if randomness_oracle.metadata() != metadata {
+            randomness_oracle.approved = false;
+        }
+
+        // Update
+        randomness_oracle.set_metadata(&metadata);
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `RequestRandomness` instruction.
+    pub fn request_randomness(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        seed: [u8; 32],
+        callback_program: Option<Pubkey>,
+        callback_accounts: Vec<CallbackAccount>,
+        callback_data: Vec<u8>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_request_info = next_account_info(account_info_iter)?;
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let requester_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let treasury_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let request_queue_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(requester_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Check randomness request account
+        assert_owned_by(randomness_request_info, program_id)?;
+        assert_data_len(randomness_request_info, RandomnessRequest::LEN)?;
+        assert_rent_exempt(rent, randomness_request_info)?;
+
+        // Check callback
+        if callback_accounts.len() > MAX_CALLBACK_ACCOUNTS
+            || callback_data.len() > MAX_CALLBACK_DATA_LEN
+            || callback_program == Some(Pubkey::default())
+        {
+            return Err(RandomnessOracleError::InvalidCallback.into());
+        }
+
+        // Check treasury
+        let (treasury_key, _) = find_treasury_address(program_id, randomness_oracle_info.key);
+        if *treasury_info.key != treasury_key {
+            return Err(RandomnessOracleError::InvalidTreasury.into());
+        }
+
+        // Get state
+        let randomness_oracle = RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+        let mut randomness_request =
+            RandomnessRequest::unpack_unchecked(&randomness_request_info.data.borrow())?;
+        assert_uninitialized(&randomness_request)?;
+
+        // Check subscription
+        let subscription_info = account_info_iter.next();
+        if let Some(subscription_info) = subscription_info {
+            assert_owned_by(subscription_info, program_id)?;
+
+            let mut subscription = Subscription::unpack(&subscription_info.data.borrow())?;
+            if subscription.randomness_oracle != *randomness_oracle_info.key
+                || subscription.owner != *requester_info.key
+            {
+                return Err(RandomnessOracleError::InvalidSubscription.into());
+            }
+
+            // Reserve request fee, paid on fulfillment
+            subscription.reserve(randomness_oracle.request_fee)?;
+            Subscription::pack(subscription, *subscription_info.data.borrow_mut())?;
+        }
+
+        // Initialize
+        randomness_request.init(InitRandomnessRequestParams {
+            randomness_oracle: *randomness_oracle_info.key,
+            requester: *requester_info.key,
+            seed,
+            callback_program: callback_program.unwrap_or_default(),
+            callback_accounts,
+            callback_data,
+            slot: clock.slot,
+            subscription: subscription_info.map(|info| *info.key),
+            fee: randomness_oracle.request_fee,
+        });
+
+        // Queue until fulfilled or cancelled
+        Self::enqueue_request(
+            program_id,
+            randomness_oracle_info.key,
+            randomness_request_info.key,
+            requester_info,
+            request_queue_info,
+            system_program_info,
+            rent,
+        )?;
+
+        // Pay request fee, unless reserved in subscription
+        if subscription_info.is_none() && randomness_oracle.request_fee > 0 {
+            invoke(
+                &system_instruction::transfer(
+                    requester_info.key,
+                    treasury_info.key,
+                    randomness_oracle.request_fee,
+                ),
+                &[
+                    requester_info.clone(),
+                    treasury_info.clone(),
+                    system_program_info.clone(),
+                ],
+            )?;
+        }
+
+        // Save state
+        RandomnessRequest::pack(
+            randomness_request,
+            *randomness_request_info.data.borrow_mut(),
+        )?;
+
+        Ok(())
+    }
+
+    /// Process `FulfillRandomness` instruction.
+    pub fn fulfill_randomness(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_request_info = next_account_info(account_info_iter)?;
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+        let slot_hashes_info = next_account_info(account_info_iter)?;
+        let request_queue_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Check randomness request owner
+        assert_owned_by(randomness_request_info, program_id)?;
+
+        // Get state
+        let randomness_oracle = RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+        let mut randomness_request =
+            RandomnessRequest::unpack(&randomness_request_info.data.borrow())?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Check random oracle is not paused
+        if randomness_oracle.paused {
+            return Err(RandomnessOracleError::OraclePaused.into());
+        }
+
+        // Check randomness request
+        if randomness_request.randomness_oracle != *randomness_oracle_info.key {
+            return Err(RandomnessOracleError::InvalidRandomnessRequest.into());
+        }
+        if randomness_request.fulfilled {
+            return Err(RandomnessOracleError::RequestAlreadyFulfilled.into());
+        }
+
+        // Request slot hash wasn't known to anyone when the request was made
+        let slot_hash = get_slot_hash(slot_hashes_info, randomness_request.slot)?;
+
+        // Remove from queue
+        Self::dequeue_request(
+            program_id,
+            randomness_oracle_info.key,
+            randomness_request_info.key,
+            request_queue_info,
+        )?;
+
+        // Pay reserved request fee from subscription
+        if let Some(subscription_key) = randomness_request.subscription() {
+            let subscription_info = next_account_info(account_info_iter)?;
+            let treasury_info = next_account_info(account_info_iter)?;
+
+            if *subscription_info.key != subscription_key {
+                return Err(RandomnessOracleError::InvalidSubscription.into());
+            }
+            assert_owned_by(subscription_info, program_id)?;
+
+            let (treasury_key, _) = find_treasury_address(program_id, randomness_oracle_info.key);
+            if *treasury_info.key != treasury_key {
+                return Err(RandomnessOracleError::InvalidTreasury.into());
+            }
+
+            let mut subscription = Subscription::unpack(&subscription_info.data.borrow())?;
+            subscription.settle()?;
+            Subscription::pack(subscription, *subscription_info.data.borrow_mut())?;
+
+            **subscription_info.lamports.borrow_mut() = subscription_info
+                .lamports()
+                .checked_sub(randomness_request.fee)
+                .ok_or(ProgramError::InsufficientFunds)?;
+            **treasury_info.lamports.borrow_mut() = treasury_info
+                .lamports()
+                .checked_add(randomness_request.fee)
+                .ok_or(ProgramError::InvalidArgument)?;
+        }
+
+        // Fulfill, binding the value to the request so it can't be reused
+        let value = hashv(&[
+            slot_hash.as_ref(),
+            &randomness_request.seed,
+            randomness_request_info.key.as_ref(),
+        ])
+        .to_bytes();
+        randomness_request.fulfill(value, clock.slot);
+
+        // Save state before the callback reads it
+        RandomnessRequest::pack(
+            randomness_request.clone(),
+            *randomness_request_info.data.borrow_mut(),
+        )?;
+
+        // Invoke callback
+        let callback_program = match randomness_request.callback_program() {
+            Some(callback_program) => callback_program,
+            None => return Ok(()),
+        };
+        let callback_program_info = next_account_info(account_info_iter)?;
+        if *callback_program_info.key != callback_program {
+            return Err(RandomnessOracleError::InvalidCallback.into());
+        }
+
+        let mut callback_account_infos = vec![randomness_request_info.clone()];
+        for callback_account in randomness_request.callback_accounts() {
+            let callback_account_info = next_account_info(account_info_iter)?;
+            if *callback_account_info.key != callback_account.pubkey {
+                return Err(RandomnessOracleError::InvalidCallback.into());
+            }
+            callback_account_infos.push(callback_account_info.clone());
+        }
+        callback_account_infos.push(callback_program_info.clone());
+
+        invoke(
+            &randomness_callback(
+                &callback_program,
+                randomness_request_info.key,
+                randomness_request.callback_accounts(),
+                randomness_request.callback_data(),
+            ),
+            &callback_account_infos,
+        )
+    }
+
+    /// Process `SetRequestFee` instruction.
+    pub fn set_request_fee(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        request_fee: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Update
+        randomness_oracle.request_fee = request_fee;
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `CreateSubscription` instruction.
+    pub fn create_subscription(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let subscription_info = next_account_info(account_info_iter)?;
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(owner_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Random oracle has to be initialized
+        RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check subscription address
+        let (subscription_key, bump_seed) =
+            find_subscription_address(program_id, randomness_oracle_info.key, owner_info.key);
+        if subscription_key != *subscription_info.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        // Create subscription account
+        create_program_derived_account(
+            owner_info,
+            subscription_info,
+            system_program_info,
+            rent,
+            Subscription::LEN,
+            program_id,
+            &[
+                SUBSCRIPTION_SEED,
+                randomness_oracle_info.key.as_ref(),
+                owner_info.key.as_ref(),
+                &[bump_seed],
+            ],
+        )?;
+
+        // Get state
+        let mut subscription = Subscription::unpack_unchecked(&subscription_info.data.borrow())?;
+
+        // Initialize
+        subscription.init(InitSubscriptionParams {
+            randomness_oracle: *randomness_oracle_info.key,
+            owner: *owner_info.key,
+        });
+
+        // Save state
+        Subscription::pack(subscription, *subscription_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `FundSubscription` instruction.
+    pub fn fund_subscription(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let subscription_info = next_account_info(account_info_iter)?;
+        let funder_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(funder_info)?;
+
+        // Check subscription owner
+        assert_owned_by(subscription_info, program_id)?;
+
+        // Get state
+        let mut subscription = Subscription::unpack(&subscription_info.data.borrow())?;
+
+        // Fund
+        subscription.fund(amount)?;
+        invoke(
+            &system_instruction::transfer(funder_info.key, subscription_info.key, amount),
+            &[
+                funder_info.clone(),
+                subscription_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+
+        // Save state
+        Subscription::pack(subscription, *subscription_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `CancelSubscription` instruction.
+    pub fn cancel_subscription(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let subscription_info = next_account_info(account_info_iter)?;
+        let owner_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(owner_info)?;
+
+        // Check subscription owner
+        assert_owned_by(subscription_info, program_id)?;
+
+        // Get state
+        let subscription = Subscription::unpack(&subscription_info.data.borrow())?;
+
+        // Check subscription owner
+        if subscription.owner != *owner_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Reserved fees belong to pending requests
+        if subscription.pending_requests > 0 {
+            return Err(RandomnessOracleError::SubscriptionHasPendingRequests.into());
+        }
+
+        // Close
+        Subscription::pack(
+            Subscription::default(),
+            *subscription_info.data.borrow_mut(),
+        )?;
+
+        // Transfer all lamports
+        let lamports = subscription_info.lamports();
+        **destination_info.lamports.borrow_mut() = destination_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **subscription_info.lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /// Process `WithdrawFees` instruction.
+    pub fn withdraw_fees(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let treasury_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Get state
+        let randomness_oracle = RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Transfer all lamports
+        Self::withdraw_treasury(
+            program_id,
+            randomness_oracle_info,
+            treasury_info,
+            destination_info,
+            system_program_info,
+        )
+    }
+
+    /// Process `SlashOperator` instruction.
+    pub fn slash_operator(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let bond_vault_info = next_account_info(account_info_iter)?;
+        let caller_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+
+        // Check signer
+        assert_signer(caller_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check reveal deadline
+        if !randomness_oracle.is_reveal_expired(clock.slot) {
+            return Err(RandomnessOracleError::RevealNotExpired.into());
+        }
+
+        // Slash, bond vault left below rent exempt minimum is slashed whole
+        let bond = bond_vault_info.lamports();
+        let mut lamports = bond / SLASH_DIVISOR;
+        if bond - lamports < rent.minimum_balance(0) {
+            lamports = bond;
+        }
+
+        randomness_oracle.slash();
+        Self::withdraw_bond(
+            program_id,
+            randomness_oracle_info,
+            bond_vault_info,
+            caller_info,
+            system_program_info,
+            lamports,
+        )?;
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `PauseRandomnessOracle` instruction.
+    pub fn pause_randomness_oracle(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        Self::set_paused(program_id, accounts, true)
+    }
+
+    /// Process `ResumeRandomnessOracle` instruction.
+    pub fn resume_randomness_oracle(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        Self::set_paused(program_id, accounts, false)
+    }
+
+    /// Set random oracle paused flag by its authority.
+    fn set_paused(program_id: &Pubkey, accounts: &[AccountInfo], paused: bool) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Update
+        randomness_oracle.paused = paused;
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `InitProgramConfig` instruction.
+    pub fn init_program_config(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        admin: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let program_config_info = next_account_info(account_info_iter)?;
+        let upgrade_authority_info = next_account_info(account_info_iter)?;
+        let program_data_info = next_account_info(account_info_iter)?;
+        let rent_info = next_account_info(account_info_iter)?;
+        let rent = &Rent::from_account_info(rent_info)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(upgrade_authority_info)?;
+
+        // Check upgrade authority
+        if get_upgrade_authority(program_id, program_data_info)?
+            != Some(*upgrade_authority_info.key)
+        {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Check program config address
+        let (program_config_key, bump_seed) = find_program_config_address(program_id);
+        if program_config_key != *program_config_info.key {
+            return Err(ProgramError::InvalidSeeds);
+        }
+
+        // Create program config account
+        create_program_derived_account(
+            upgrade_authority_info,
+            program_config_info,
+            system_program_info,
+            rent,
+            ProgramConfig::LEN,
+            program_id,
+            &[PROGRAM_CONFIG_SEED, &[bump_seed]],
+        )?;
+
+        // Get state
+        let mut program_config =
+            ProgramConfig::unpack_unchecked(&program_config_info.data.borrow())?;
+        assert_uninitialized(&program_config)?;
+
+        // Initialize
+        program_config.init(InitProgramConfigParams { admin });
+
+        // Save state
+        ProgramConfig::pack(program_config, *program_config_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `ApproveOracle` instruction.
+    pub fn approve_oracle(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        Self::set_approved(program_id, accounts, true)
+    }
+
+    /// Process `RevokeOracle` instruction.
+    pub fn revoke_oracle(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        Self::set_approved(program_id, accounts, false)
+    }
+
+    /// Process `UpdateRandomnessOracles` instruction.
+    pub fn update_randomness_oracles(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        values: Vec<[u8; 32]>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let authority_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+
+        // Check signer
+        assert_signer(authority_info)?;
+
+        // Keep the batch within the compute budget
+        if values.len() > MAX_BATCH_UPDATES {
+            return Err(RandomnessOracleError::TooManyUpdates.into());
+        }
+
+        for value in values {
+            let randomness_oracle_info = next_account_info(account_info_iter)?;
+
+            Self::apply_update(
+                program_id,
+                randomness_oracle_info,
+                authority_info,
+                &clock,
+                account_info_iter,
+                UpdateMode::Direct,
+                |randomness_oracle| {
+                    randomness_oracle.update(value, clock.slot, clock.unix_timestamp);
+                    Ok(())
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Process `ProposeAdmin` instruction.
+    pub fn propose_admin(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_admin: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let program_config_info = next_account_info(account_info_iter)?;
+        let admin_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(admin_info)?;
+
+        // Check program config
+        let (program_config_key, _) = find_program_config_address(program_id);
+        if program_config_key != *program_config_info.key {
+            return Err(RandomnessOracleError::InvalidProgramConfig.into());
+        }
+        assert_owned_by(program_config_info, program_id)?;
+
+        // Get state
+        let mut program_config = ProgramConfig::unpack(&program_config_info.data.borrow())?;
+
+        // Check program config admin
+        if program_config.admin != *admin_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Propose
+        program_config.propose_admin(new_admin);
+
+        // Save state
+        ProgramConfig::pack(program_config, *program_config_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `AcceptAdmin` instruction.
+    pub fn accept_admin(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let program_config_info = next_account_info(account_info_iter)?;
+        let new_admin_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(new_admin_info)?;
+
+        // Check program config
+        let (program_config_key, _) = find_program_config_address(program_id);
+        if program_config_key != *program_config_info.key {
+            return Err(RandomnessOracleError::InvalidProgramConfig.into());
+        }
+        assert_owned_by(program_config_info, program_id)?;
+
+        // Get state
+        let mut program_config = ProgramConfig::unpack(&program_config_info.data.borrow())?;
+
+        // Check proposed admin
+        if !program_config.is_pending_admin(new_admin_info.key) {
+            return Err(RandomnessOracleError::InvalidPendingAuthority.into());
+        }
+
+        // Accept
+        program_config.accept_admin();
+
+        // Save state
+        ProgramConfig::pack(program_config, *program_config_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Process `CancelRandomnessRequest` instruction.
+    pub fn cancel_randomness_request(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let randomness_request_info = next_account_info(account_info_iter)?;
+        let caller_info = next_account_info(account_info_iter)?;
+        let destination_info = next_account_info(account_info_iter)?;
+        let clock_info = next_account_info(account_info_iter)?;
+        let clock = solana_program::clock::Clock::from_account_info(clock_info)?;
+        let request_queue_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(caller_info)?;
+
+        // Check randomness request owner
+        assert_owned_by(randomness_request_info, program_id)?;
+
+        // Lamports can't be returned to the closed account itself
+        if randomness_request_info.key == destination_info.key {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        // Get state
+        let randomness_request = RandomnessRequest::unpack(&randomness_request_info.data.borrow())?;
+
+        // Anyone can evict expired request, but only the requester can take its lamports
+        if randomness_request.requester != *caller_info.key
+            && randomness_request.requester != *destination_info.key
+        {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Check randomness request
+        if randomness_request.fulfilled {
+            return Err(RandomnessOracleError::RequestAlreadyFulfilled.into());
+        }
+        if !randomness_request.is_expired(clock.slot) {
+            return Err(RandomnessOracleError::RequestNotExpired.into());
+        }
+
+        // Remove from queue
+        Self::dequeue_request(
+            program_id,
+            &randomness_request.randomness_oracle,
+            randomness_request_info.key,
+            request_queue_info,
+        )?;
+
+        // Return reserved request fee to subscription
+        if let Some(subscription_key) = randomness_request.subscription() {
+            let subscription_info = next_account_info(account_info_iter)?;
+            if *subscription_info.key != subscription_key {
+                return Err(RandomnessOracleError::InvalidSubscription.into());
+            }
+            assert_owned_by(subscription_info, program_id)?;
+
+            let mut subscription = Subscription::unpack(&subscription_info.data.borrow())?;
+            subscription.refund(randomness_request.fee)?;
+            Subscription::pack(subscription, *subscription_info.data.borrow_mut())?;
+        }
+
+        // Close
+        RandomnessRequest::pack(
+            RandomnessRequest::default(),
+            *randomness_request_info.data.borrow_mut(),
+        )?;
+
+        // Transfer all lamports
+        let lamports = randomness_request_info.lamports();
+        **destination_info.lamports.borrow_mut() = destination_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **randomness_request_info.lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /// Update random oracle by its authority, then record history, emit event and save state.
+    ///
+    /// `update` makes checks specific to the instruction and writes the new value.
+    fn apply_update<'a, 'b: 'a, I, F>(
+        program_id: &Pubkey,
+        randomness_oracle_info: &AccountInfo<'b>,
+        authority_info: &AccountInfo<'b>,
+        clock: &solana_program::clock::Clock,
+        account_info_iter: &mut I,
+        update_mode: UpdateMode,
+        update: F,
+    ) -> ProgramResult
+    where
+        I: Iterator<Item = &'a AccountInfo<'b>>,
+        F: FnOnce(&mut RandomnessOracle) -> ProgramResult,
+    {
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Get state
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check random oracle authority
+        if randomness_oracle.authority != *authority_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Check random oracle is not paused
+        if randomness_oracle.paused {
+            return Err(RandomnessOracleError::OraclePaused.into());
+        }
+
+        // Check random oracle update mode
+        if randomness_oracle.update_mode != update_mode {
+            return Err(RandomnessOracleError::InvalidUpdateMode.into());
+        }
+
+        // Check minimum update interval
+        if !randomness_oracle.can_update(clock.slot) {
+            return Err(RandomnessOracleError::UpdateTooEarly.into());
+        }
+
+        // Update
+        update(&mut randomness_oracle)?;
+
+        // Record history
+        Self::record_history(program_id, &randomness_oracle, account_info_iter)?;
+
+        // Emit event
+        RandomnessOracleEvent::new(
+            RandomnessOracleEventKind::Updated,
+            randomness_oracle_info.key,
+            &randomness_oracle,
+        )
+        .emit();
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())
+    }
+
+    /// Set random oracle approved flag by the program config admin.
+    fn set_approved(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        approved: bool,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let program_config_info = next_account_info(account_info_iter)?;
+        let admin_info = next_account_info(account_info_iter)?;
+        let randomness_oracle_info = next_account_info(account_info_iter)?;
+
+        // Check signer
+        assert_signer(admin_info)?;
+
+        // Check program config
+        let (program_config_key, _) = find_program_config_address(program_id);
+        if program_config_key != *program_config_info.key {
+            return Err(RandomnessOracleError::InvalidProgramConfig.into());
+        }
+        assert_owned_by(program_config_info, program_id)?;
+
+        // Check random oracle owner
+        assert_owned_by(randomness_oracle_info, program_id)?;
+
+        // Get state
+        let program_config = ProgramConfig::unpack(&program_config_info.data.borrow())?;
+        let mut randomness_oracle =
+            RandomnessOracle::unpack(&randomness_oracle_info.data.borrow())?;
+
+        // Check program config admin
+        if program_config.admin != *admin_info.key {
+            return Err(RandomnessOracleError::InvalidAuthority.into());
+        }
+
+        // Update
+        randomness_oracle.approved = approved;
+
+        // Save state
+        RandomnessOracle::pack(randomness_oracle, *randomness_oracle_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Add random oracle to the registry of the authority, creating the registry on first use.
+    fn register<'a>(
+        program_id: &Pubkey,
+        randomness_oracle: &Pubkey,
+        authority_info: &AccountInfo<'a>,
+        registry_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        rent: &Rent,
+    ) -> ProgramResult {
+        let (registry_key, bump_seed) = find_registry_address(program_id, authority_info.key);
+        if *registry_info.key != registry_key {
+            return Err(RandomnessOracleError::InvalidRegistry.into());
+        }
+
+        if registry_info.data_len() == 0 {
+            create_program_derived_account(
+                authority_info,
+                registry_info,
+                system_program_info,
+                rent,
+                RandomnessOracleRegistry::LEN,
+                program_id,
+                &[REGISTRY_SEED, authority_info.key.as_ref(), &[bump_seed]],
+            )?;
+        }
+        assert_owned_by(registry_info, program_id)?;
+
+        let mut registry =
+            RandomnessOracleRegistry::unpack_unchecked(&registry_info.data.borrow())?;
+        if !registry.is_initialized() {
+            registry.init(InitRandomnessOracleRegistryParams {
+                authority: *authority_info.key,
+            });
+        }
+        registry.add(*randomness_oracle)?;
+
+        RandomnessOracleRegistry::pack(registry, *registry_info.data.borrow_mut())
+    }
+
+    /// Remove random oracle from the registry of the authority.
+    fn unregister(
+        program_id: &Pubkey,
+        randomness_oracle: &Pubkey,
+        authority: &Pubkey,
+        registry_info: &AccountInfo,
+    ) -> ProgramResult {
+        let (registry_key, _) = find_registry_address(program_id, authority);
+        if *registry_info.key != registry_key {
+            return Err(RandomnessOracleError::InvalidRegistry.into());
+        }
+        assert_owned_by(registry_info, program_id)?;
+
+        let mut registry = RandomnessOracleRegistry::unpack(&registry_info.data.borrow())?;
+        registry.remove(randomness_oracle);
+
+        RandomnessOracleRegistry::pack(registry, *registry_info.data.borrow_mut())
+    }
+
+    /// Add randomness request to the queue of the random oracle, creating the queue on first use.
+    fn enqueue_request<'a>(
+        program_id: &Pubkey,
+        randomness_oracle: &Pubkey,
+        randomness_request: &Pubkey,
+        requester_info: &AccountInfo<'a>,
+        request_queue_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        rent: &Rent,
+    ) -> ProgramResult {
+        let (request_queue_key, bump_seed) =
+            find_request_queue_address(program_id, randomness_oracle);
+        if *request_queue_info.key != request_queue_key {
+            return Err(RandomnessOracleError::InvalidRequestQueue.into());
+        }
+
+        if request_queue_info.data_len() == 0 {
+            create_program_derived_account(
+                requester_info,
+                request_queue_info,
+                system_program_info,
+                rent,
+                RandomnessRequestQueue::LEN,
+                program_id,
+                &[REQUEST_QUEUE_SEED, randomness_oracle.as_ref(), &[bump_seed]],
+            )?;
+        }
+        assert_owned_by(request_queue_info, program_id)?;
+
+        let mut request_queue =
+            RandomnessRequestQueue::unpack_unchecked(&request_queue_info.data.borrow())?;
+        if !request_queue.is_initialized() {
+            request_queue.init(InitRandomnessRequestQueueParams {
+                randomness_oracle: *randomness_oracle,
+            });
+        }
+        request_queue.push(*randomness_request)?;
+
+        RandomnessRequestQueue::pack(request_queue, *request_queue_info.data.borrow_mut())
+    }
+
+    /// Remove randomness request from the queue of the random oracle.
+    fn dequeue_request(
+        program_id: &Pubkey,
+        randomness_oracle: &Pubkey,
+        randomness_request: &Pubkey,
+        request_queue_info: &AccountInfo,
+    ) -> ProgramResult {
+        let (request_queue_key, _) = find_request_queue_address(program_id, randomness_oracle);
+        if *request_queue_info.key != request_queue_key {
+            return Err(RandomnessOracleError::InvalidRequestQueue.into());
+        }
+        assert_owned_by(request_queue_info, program_id)?;
+
+        let mut request_queue = RandomnessRequestQueue::unpack(&request_queue_info.data.borrow())?;
+        request_queue.remove(randomness_request);
+
+        RandomnessRequestQueue::pack(request_queue, *request_queue_info.data.borrow_mut())
+    }
+
+    /// Transfer bond from authority into random oracle bond vault.
+    fn deposit_bond<'a>(
+        program_id: &Pubkey,
+        randomness_oracle_info: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        bond_vault_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        rent: &Rent,
+        bond: u64,
+    ) -> ProgramResult {
+        let (bond_vault_key, _) = find_bond_vault_address(program_id, randomness_oracle_info.key);
+        if *bond_vault_info.key != bond_vault_key {
+            return Err(RandomnessOracleError::InvalidBondVault.into());
+        }
+
+        // Bond vault has to stay rent exempt
+        if bond < MIN_BOND_LAMPORTS.max(rent.minimum_balance(0)) {
+            return Err(RandomnessOracleError::InsufficientBond.into());
+        }
+
+        invoke(
+            &system_instruction::transfer(authority_info.key, bond_vault_info.key, bond),
+            &[
+                authority_info.clone(),
+                bond_vault_info.clone(),
+                system_program_info.clone(),
+            ],
+        )
+    }
+
+    /// Transfer lamports from random oracle bond vault.
+    fn withdraw_bond<'a>(
+        program_id: &Pubkey,
+        randomness_oracle_info: &AccountInfo<'a>,
+        bond_vault_info: &AccountInfo<'a>,
+        destination_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+        lamports: u64,
+    ) -> ProgramResult {
+        let (bond_vault_key, bump_seed) =
+            find_bond_vault_address(program_id, randomness_oracle_info.key);
+        if *bond_vault_info.key != bond_vault_key {
+            return Err(RandomnessOracleError::InvalidBondVault.into());
+        }
+
+        if lamports == 0 {
+            return Ok(());
+        }
+
+        invoke_signed(
+            &system_instruction::transfer(bond_vault_info.key, destination_info.key, lamports),
+            &[
+                bond_vault_info.clone(),
+                destination_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[
+                BOND_VAULT_SEED,
+                randomness_oracle_info.key.as_ref(),
+                &[bump_seed],
+            ]],
+        )
+    }
+
+    /// Transfer all collected fees from random oracle treasury.
+    fn withdraw_treasury<'a>(
+        program_id: &Pubkey,
+        randomness_oracle_info: &AccountInfo<'a>,
+        treasury_info: &AccountInfo<'a>,
+        destination_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let (treasury_key, bump_seed) =
+            find_treasury_address(program_id, randomness_oracle_info.key);
+        if *treasury_info.key != treasury_key {
+            return Err(RandomnessOracleError::InvalidTreasury.into());
+        }
+
+        let lamports = treasury_info.lamports();
+        if lamports == 0 {
+            return Ok(());
+        }
+
+        invoke_signed(
+            &system_instruction::transfer(treasury_info.key, destination_info.key, lamports),
+            &[
+                treasury_info.clone(),
+                destination_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[
+                TREASURY_SEED,
+                randomness_oracle_info.key.as_ref(),
+                &[bump_seed],
+            ]],
+        )
+    }
+
+    /// Close random oracle request queue if it was created.
+    fn close_request_queue(
+        program_id: &Pubkey,
+        randomness_oracle: &Pubkey,
+        request_queue_info: &AccountInfo,
+        destination_info: &AccountInfo,
+    ) -> ProgramResult {
+        let (request_queue_key, _) = find_request_queue_address(program_id, randomness_oracle);
+        if *request_queue_info.key != request_queue_key {
+            return Err(RandomnessOracleError::InvalidRequestQueue.into());
+        }
+
+        // Request queue is created by the first request
+        if request_queue_info.data_len() == 0 {
+            return Ok(());
+        }
+        assert_owned_by(request_queue_info, program_id)?;
+
+        let request_queue = RandomnessRequestQueue::unpack(&request_queue_info.data.borrow())?;
+        if !request_queue.requests().is_empty() {
+            return Err(RandomnessOracleError::RequestQueueNotEmpty.into());
+        }
+
+        // Clear data
+        for byte in request_queue_info.data.borrow_mut().iter_mut() {
+            *byte = 0;
+        }
+
+        // Transfer all lamports
+        let lamports = request_queue_info.lamports();
+        **destination_info.lamports.borrow_mut() = destination_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **request_queue_info.lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /// Push the current value of random oracle into its history if history is kept.
+    fn record_history<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+        program_id: &Pubkey,
+        randomness_oracle: &RandomnessOracle,
+        account_info_iter: &mut I,
+    ) -> ProgramResult {
+        let history_key = match randomness_oracle.history_address() {
+            Some(history_key) => history_key,
+            None => return Ok(()),
+        };
+        let history_info = next_account_info(account_info_iter)?;
+
+        // Check history account
+        if *history_info.key != history_key {
+            return Err(RandomnessOracleError::InvalidHistory.into());
+        }
+        assert_owned_by(history_info, program_id)?;
+
+        // Get state
+        let mut history = RandomnessOracleHistory::unpack(&history_info.data.borrow())?;
+
+        // Record
+        history.push(
+            randomness_oracle.slot,
+            randomness_oracle.round,
+            randomness_oracle.value,
+        );
+
+        // Save state
+        RandomnessOracleHistory::pack(history, *history_info.data.borrow_mut())?;
+
+        Ok(())
+    }
+
+    /// Close history of random oracle if history is kept, transferring its lamports to destination.
+    fn close_history<'a, 'b: 'a, I: Iterator<Item = &'a AccountInfo<'b>>>(
+        program_id: &Pubkey,
+        randomness_oracle: &RandomnessOracle,
+        destination_info: &AccountInfo<'b>,
+        account_info_iter: &mut I,
+    ) -> ProgramResult {
+        let history_key = match randomness_oracle.history_address() {
+            Some(history_key) => history_key,
+            None => return Ok(()),
+        };
+        let history_info = next_account_info(account_info_iter)?;
+
+        // Check history account
+        if *history_info.key != history_key {
+            return Err(RandomnessOracleError::InvalidHistory.into());
+        }
+        assert_owned_by(history_info, program_id)?;
+
+        // Clear data
+        for byte in history_info.data.borrow_mut().iter_mut() {
+            *byte = 0;
+        }
+
+        // Transfer all lamports
+        let lamports = history_info.lamports();
+        **destination_info.lamports.borrow_mut() = destination_info
+            .lamports()
+            .checked_add(lamports)
+            .ok_or(ProgramError::InvalidArgument)?;
+        **history_info.lamports.borrow_mut() = 0;
+
+        Ok(())
+    }
+
+    /// Instruction processing router.
+    pub fn process_instruction(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        input: &[u8],
+    ) -> ProgramResult {
+        let instruction = RandomnessOracleInstruction::try_from_slice(input)?;
+
+        match instruction {
+            RandomnessOracleInstruction::InitRandomnessOracle {
+                min_update_interval_slots,
+                metadata,
+                bond,
+                update_mode,
+            } => {
+                msg!("RandomnessOracleInstruction: InitRandomnessOracle");
+                Self::init_randomness_oracle(
+                    program_id,
+                    accounts,
+                    min_update_interval_slots,
+                    metadata,
+                    bond,
+                    update_mode,
+                )
+            }
+            RandomnessOracleInstruction::UpdateRandomnessOracle { value } => {
+                msg!("RandomnessOracleInstruction: UpdateRandomnessOracle");
+                Self::update_randomness_oracle(program_id, accounts, value)
+            }
+            RandomnessOracleInstruction::CommitRandomness { hash } => {
+                msg!("RandomnessOracleInstruction: CommitRandomness");
+                Self::commit_randomness(program_id, accounts, hash)
+            }
+            RandomnessOracleInstruction::RevealRandomness { seed } => {
+                msg!("RandomnessOracleInstruction: RevealRandomness");
+                Self::reveal_randomness(program_id, accounts, seed)
+            }
+            RandomnessOracleInstruction::MixRandomnessOracle { value } => {
+                msg!("RandomnessOracleInstruction: MixRandomnessOracle");
+                Self::mix_randomness_oracle(program_id, accounts, value)
+            }
+            RandomnessOracleInstruction::UpdateRandomnessOracleWithSignature { signature } => {
+                msg!("RandomnessOracleInstruction: UpdateRandomnessOracleWithSignature");
+                Self::update_randomness_oracle_with_signature(program_id, accounts, signature)
+            }
+            RandomnessOracleInstruction::ProposeAuthority { new_authority } => {
+                msg!("RandomnessOracleInstruction: ProposeAuthority");
+                Self::propose_authority(program_id, accounts, new_authority)
+            }
+            RandomnessOracleInstruction::AcceptAuthority => {
+                msg!("RandomnessOracleInstruction: AcceptAuthority");
+                Self::accept_authority(program_id, accounts)
+            }
+            RandomnessOracleInstruction::CloseRandomnessOracle => {
+                msg!("RandomnessOracleInstruction: CloseRandomnessOracle");
+                Self::close_randomness_oracle(program_id, accounts)
+            }
+            RandomnessOracleInstruction::InitRandomnessOracleHistory => {
+                msg!("RandomnessOracleInstruction: InitRandomnessOracleHistory");
+                Self::init_randomness_oracle_history(program_id, accounts)
+            }
+            RandomnessOracleInstruction::SetMinUpdateInterval {
+                min_update_interval_slots,
+            } => {
+                msg!("RandomnessOracleInstruction: SetMinUpdateInterval");
+                Self::set_min_update_interval(program_id, accounts, min_update_interval_slots)
+            }
+            RandomnessOracleInstruction::CreateRandomnessOracle {
+                metadata,
+                min_update_interval_slots,
+                bond,
+                update_mode,
+            } => {
+                msg!("RandomnessOracleInstruction: CreateRandomnessOracle");
+                Self::create_randomness_oracle(
+                    program_id,
+                    accounts,
+                    metadata,
+                    min_update_interval_slots,
+                    bond,
+                    update_mode,
+                )
+            }
+            RandomnessOracleInstruction::MigrateRandomnessOracle => {
+                msg!("RandomnessOracleInstruction: MigrateRandomnessOracle");
+                Self::migrate_randomness_oracle(program_id, accounts)
+            }
+            RandomnessOracleInstruction::UpdateMetadata { metadata } => {
+                msg!("RandomnessOracleInstruction: UpdateMetadata");
+                Self::update_metadata(program_id, accounts, metadata)
+            }
+            RandomnessOracleInstruction::RequestRandomness {
+                seed,
+                callback_program,
+                callback_accounts,
+                callback_data,
+            } => {
+                msg!("RandomnessOracleInstruction: RequestRandomness");
+                Self::request_randomness(
+                    program_id,
+                    accounts,
+                    seed,
+                    callback_program,
+                    callback_accounts,
+                    callback_data,
+                )
+            }
+            RandomnessOracleInstruction::FulfillRandomness => {
+                msg!("RandomnessOracleInstruction: FulfillRandomness");
+                Self::fulfill_randomness(program_id, accounts)
+            }
+            RandomnessOracleInstruction::SetRequestFee { request_fee } => {
+                msg!("RandomnessOracleInstruction: SetRequestFee");
+                Self::set_request_fee(program_id, accounts, request_fee)
+            }
+            RandomnessOracleInstruction::WithdrawFees => {
+                msg!("RandomnessOracleInstruction: WithdrawFees");
+                Self::withdraw_fees(program_id, accounts)
+            }
+            RandomnessOracleInstruction::CreateSubscription => {
+                msg!("RandomnessOracleInstruction: CreateSubscription");
+                Self::create_subscription(program_id, accounts)
+            }
+            RandomnessOracleInstruction::FundSubscription { amount } => {
+                msg!("RandomnessOracleInstruction: FundSubscription");
+                Self::fund_subscription(program_id, accounts, amount)
+            }
+            RandomnessOracleInstruction::CancelSubscription => {
+                msg!("RandomnessOracleInstruction: CancelSubscription");
+                Self::cancel_subscription(program_id, accounts)
+            }
+            RandomnessOracleInstruction::SlashOperator => {
+                msg!("RandomnessOracleInstruction: SlashOperator");
+                Self::slash_operator(program_id, accounts)
+            }
+            RandomnessOracleInstruction::PauseRandomnessOracle => {
+                msg!("RandomnessOracleInstruction: PauseRandomnessOracle");
+                Self::pause_randomness_oracle(program_id, accounts)
+            }
+            RandomnessOracleInstruction::ResumeRandomnessOracle => {
+                msg!("RandomnessOracleInstruction: ResumeRandomnessOracle");
+                Self::resume_randomness_oracle(program_id, accounts)
+            }
+            RandomnessOracleInstruction::InitProgramConfig { admin } => {
+                msg!("RandomnessOracleInstruction: InitProgramConfig");
+                Self::init_program_config(program_id, accounts, admin)
+            }
+            RandomnessOracleInstruction::ApproveOracle => {
+                msg!("RandomnessOracleInstruction: ApproveOracle");
+                Self::approve_oracle(program_id, accounts)
+            }
+            RandomnessOracleInstruction::RevokeOracle => {
+                msg!("RandomnessOracleInstruction: RevokeOracle");
+                Self::revoke_oracle(program_id, accounts)
+            }
+            RandomnessOracleInstruction::UpdateRandomnessOracles { values } => {
+                msg!("RandomnessOracleInstruction: UpdateRandomnessOracles");
+                Self::update_randomness_oracles(program_id, accounts, values)
+            }
+            RandomnessOracleInstruction::ProposeAdmin { new_admin } => {
+                msg!("RandomnessOracleInstruction: ProposeAdmin");
+                Self::propose_admin(program_id, accounts, new_admin)
+            }
+            RandomnessOracleInstruction::AcceptAdmin => {
+                msg!("RandomnessOracleInstruction: AcceptAdmin");
+                Self::accept_admin(program_id, accounts)
+            }
+            RandomnessOracleInstruction::CancelRandomnessRequest => {
+                msg!("RandomnessOracleInstruction: CancelRandomnessRequest");
+                Self::cancel_randomness_request(program_id, accounts)
             }
         }
     }
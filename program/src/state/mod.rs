@@ -1,9 +1,21 @@
 //! State types.
 
+mod program_config;
 mod randomness_oracle;
+mod randomness_oracle_history;
+mod randomness_oracle_registry;
+mod randomness_request;
+mod randomness_request_queue;
+mod subscription;
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+pub use program_config::*;
 pub use randomness_oracle::*;
+pub use randomness_oracle_history::*;
+pub use randomness_oracle_registry::*;
+pub use randomness_request::*;
+pub use randomness_request_queue::*;
+pub use subscription::*;
 
 /// Enum representing the account type managed by the program
 #[derive(Clone, Debug, PartialEq, BorshDeserialize, BorshSerialize, BorshSchema)]
@@ -12,6 +24,20 @@ pub enum AccountType {
     Uninitialized,
     /// Random oracle
     RandomnessOracle,
+    /// Closed random oracle, can't be initialized again
+    ClosedRandomnessOracle,
+    /// Random oracle history
+    RandomnessOracleHistory,
+    /// Randomness request
+    RandomnessRequest,
+    /// Subscription
+    Subscription,
+    /// Program config
+    ProgramConfig,
+    /// Random oracle registry
+    RandomnessOracleRegistry,
+    /// Randomness request queue
+    RandomnessRequestQueue,
 }
 
 impl Default for AccountType {
@@ -0,0 +1,80 @@
+//! Program config state definitions.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::AccountType;
+use crate::error::RandomnessOracleError;
+
+/// Program config initialization params.
+pub struct InitProgramConfigParams {
+    /// Admin.
+    pub admin: Pubkey,
+}
+
+/// Program wide settings, a single account per program.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct ProgramConfig {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Admin, approves and revokes randomness oracles.
+    pub admin: Pubkey,
+    /// Proposed admin, has to accept the role to become the admin.
+    pub pending_admin: Pubkey,
+}
+
+impl ProgramConfig {
+    /// Initialize a program config.
+    pub fn init(&mut self, params: InitProgramConfigParams) {
+        self.account_type = AccountType::ProgramConfig;
+        self.admin = params.admin;
+        self.pending_admin = Pubkey::default();
+    }
+
+    /// Propose a new admin, default pubkey cancels the proposal.
+    pub fn propose_admin(&mut self, new_admin: Pubkey) {
+        self.pending_admin = new_admin;
+    }
+
+    /// Check whether the given key is the proposed admin.
+    pub fn is_pending_admin(&self, admin: &Pubkey) -> bool {
+        self.pending_admin != Pubkey::default() && self.pending_admin == *admin
+    }
+
+    /// Make the proposed admin the current one.
+    pub fn accept_admin(&mut self) {
+        self.admin = self.pending_admin;
+        self.pending_admin = Pubkey::default();
+    }
+}
+
+impl Sealed for ProgramConfig {}
+
+impl Pack for ProgramConfig {
+    // 1 + 32 + 32
+    const LEN: usize = 65;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Expected LEN: {}, actual LEN: {}", Self::LEN, src.len());
+            RandomnessOracleError::DeserializationFailed.into()
+        })
+    }
+}
+
+impl IsInitialized for ProgramConfig {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::ProgramConfig
+    }
+}
@@ -2,7 +2,9 @@
 
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use solana_program::{
-    clock::Slot,
+    clock::{Slot, UnixTimestamp},
+    entrypoint::ProgramResult,
+    hash::hashv,
     msg,
     program_error::ProgramError,
     program_pack::{IsInitialized, Pack, Sealed},
@@ -10,6 +12,63 @@ use solana_program::{
 };
 
 use super::AccountType;
+use crate::error::RandomnessOracleError;
+
+/// Current random oracle layout version.
+pub const RANDOMNESS_ORACLE_VERSION: u8 = 2;
+/// Number of zeroed bytes at the end of random oracle account reserved for future fields.
+pub const RANDOMNESS_ORACLE_RESERVED_LEN: usize = 126;
+/// Number of slots after commitment to reveal the seed in, the operator can be slashed after.
+pub const MAX_REVEAL_DELAY_SLOTS: u64 = 150;
+/// Part of the bond vault balance paid to the slasher, as a divisor.
+pub const SLASH_DIVISOR: u64 = 10;
+/// Minimum operator bond, keeps the bond vault well above its rent exempt minimum.
+pub const MIN_BOND_LAMPORTS: u64 = 10_000_000;
+/// Max length of random oracle name in bytes.
+pub const MAX_NAME_LEN: usize = 32;
+/// Max length of random oracle URI in bytes.
+pub const MAX_URI_LEN: usize = 128;
+
+/// Random oracle metadata.
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct RandomnessOracleMetadata {
+    /// Name.
+    pub name: String,
+    /// URI of the oracle description.
+    pub uri: String,
+    /// Declared target number of slots between updates.
+    pub update_interval_slots: u64,
+}
+
+impl RandomnessOracleMetadata {
+    /// Check whether name and URI fit into random oracle.
+    pub fn is_valid(&self) -> bool {
+        self.name.len() <= MAX_NAME_LEN && self.uri.len() <= MAX_URI_LEN
+    }
+}
+
+/// Random oracle update mode, the only way its value can be updated.
+///
+/// Mode is fixed at initialization, so consumers can tell how values are produced.
+#[derive(Debug, Clone, Copy, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq)]
+pub enum UpdateMode {
+    /// Value is chosen by the authority, `UpdateRandomnessOracle` and `UpdateRandomnessOracles`.
+    Direct,
+    /// Value is the revealed seed of a prior commitment, `CommitRandomness` and `RevealRandomness`.
+    CommitReveal,
+    /// Value is the committed submission mixed with the slot hash of the commitment slot,
+    /// unknown to the authority when it commits, `CommitRandomness` and `MixRandomnessOracle`.
+    Mix,
+    /// Value is a hash of the committed authority signature and the slot hash of the commitment
+    /// slot, `CommitRandomness` and `UpdateRandomnessOracleWithSignature`.
+    Signature,
+}
+
+impl Default for UpdateMode {
+    fn default() -> Self {
+        UpdateMode::Direct
+    }
+}
 
 /// Random oracle initialization params.
 pub struct InitRandomnessOracleParams {
@@ -17,54 +76,349 @@ pub struct InitRandomnessOracleParams {
     pub authority: Pubkey,
     /// Current slot.
     pub slot: Slot,
+    /// Current unix timestamp.
+    pub unix_timestamp: UnixTimestamp,
+    /// Minimum number of slots between updates.
+    pub min_update_interval_slots: u64,
+    /// Metadata.
+    pub metadata: RandomnessOracleMetadata,
+    /// Update mode.
+    pub update_mode: UpdateMode,
 }
 
 /// Random oracle.
+///
+/// Version 2 layout is followed by `RANDOMNESS_ORACLE_RESERVED_LEN` zeroed bytes.
+/// New fields are appended and take space from the reserved area, so accounts keep
+/// their size and address. Accounts of an older version are upgraded in place,
+/// they read new fields as zeroes and the current version is saved on the next write.
 #[repr(C)]
-#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq)]
 pub struct RandomnessOracle {
     /// Account type.
     pub account_type: AccountType,
+    /// Layout version.
+    pub version: u8,
     /// Authority.
     pub authority: Pubkey,
     /// Random value.
     pub value: [u8; 32],
     /// Last slot.
     pub slot: Slot,
+    /// Unix timestamp of the last slot.
+    pub unix_timestamp: UnixTimestamp,
+    /// Pending commitment - hash of the seed to be revealed.
+    pub commitment: [u8; 32],
+    /// Slot of the pending commitment.
+    pub commit_slot: Slot,
+    /// Raw value submitted by authority for the last mixed update.
+    pub submission: [u8; 32],
+    /// Slot hash of the commitment slot mixed into the last mixed or signature update.
+    pub slot_hash: [u8; 32],
+    /// Proposed new authority, default pubkey if there is no proposal.
+    pub pending_authority: Pubkey,
+    /// History account, default pubkey if history is not kept.
+    pub history: Pubkey,
+    /// Number of published values, incremented on every update.
+    pub round: u64,
+    /// Minimum number of slots between updates.
+    pub min_update_interval_slots: u64,
+    /// Name, zero padded.
+    pub name: [u8; MAX_NAME_LEN],
+    /// URI of the oracle description, zero padded.
+    pub uri: [u8; MAX_URI_LEN],
+    /// Declared target number of slots between updates.
+    pub update_interval_slots: u64,
+    /// Lamports paid into the treasury for each randomness request.
+    pub request_fee: u64,
+    /// Is random oracle paused, updates are rejected and value can't be read.
+    pub paused: bool,
+    /// Is random oracle approved by the program config admin.
+    pub approved: bool,
+    /// Update mode, fixed at initialization.
+    pub update_mode: UpdateMode,
+    /// Is random oracle address derived from authority and name, the name can't be changed.
+    pub derived: bool,
+}
+
+impl Default for RandomnessOracle {
+    fn default() -> Self {
+        Self {
+            account_type: AccountType::default(),
+            version: 0,
+            authority: Pubkey::default(),
+            value: [0u8; 32],
+            slot: 0,
+            unix_timestamp: 0,
+            commitment: [0u8; 32],
+            commit_slot: 0,
+            submission: [0u8; 32],
+            slot_hash: [0u8; 32],
+            pending_authority: Pubkey::default(),
+            history: Pubkey::default(),
+            round: 0,
+            min_update_interval_slots: 0,
+            name: [0u8; MAX_NAME_LEN],
+            uri: [0u8; MAX_URI_LEN],
+            update_interval_slots: 0,
+            request_fee: 0,
+            paused: false,
+            approved: false,
+            update_mode: UpdateMode::default(),
+            derived: false,
+        }
+    }
 }
 
 impl RandomnessOracle {
     /// Initialize a random oracle.
     pub fn init(&mut self, params: InitRandomnessOracleParams) {
         self.account_type = AccountType::RandomnessOracle;
+        self.version = RANDOMNESS_ORACLE_VERSION;
         self.authority = params.authority;
         self.value = [0u8; 32];
         self.slot = params.slot;
+        self.unix_timestamp = params.unix_timestamp;
+        self.commitment = [0u8; 32];
+        self.commit_slot = 0;
+        self.submission = [0u8; 32];
+        self.slot_hash = [0u8; 32];
+        self.pending_authority = Pubkey::default();
+        self.history = Pubkey::default();
+        self.round = 0;
+        self.min_update_interval_slots = params.min_update_interval_slots;
+        self.set_metadata(&params.metadata);
+        self.request_fee = 0;
+        self.paused = false;
+        self.approved = false;
+        self.update_mode = params.update_mode;
+        self.derived = false;
+    }
+
+    /// Metadata.
+    pub fn metadata(&self) -> RandomnessOracleMetadata {
+        RandomnessOracleMetadata {
+            name: unpad(&self.name),
+            uri: unpad(&self.uri),
+            update_interval_slots: self.update_interval_slots,
+        }
+    }
+
+    /// Set metadata, name and URI must be checked with `RandomnessOracleMetadata::is_valid`.
+    pub fn set_metadata(&mut self, metadata: &RandomnessOracleMetadata) {
+        self.name = [0u8; MAX_NAME_LEN];
+        self.name[..metadata.name.len()].copy_from_slice(metadata.name.as_bytes());
+        self.uri = [0u8; MAX_URI_LEN];
+        self.uri[..metadata.uri.len()].copy_from_slice(metadata.uri.as_bytes());
+        self.update_interval_slots = metadata.update_interval_slots;
     }
 
-    /// Update random oracle.
-    pub fn update(&mut self, value: [u8; 32], slot: Slot) {
+    /// Check whether enough slots passed since the last update.
+    pub fn can_update(&self, slot: Slot) -> bool {
+        slot.saturating_sub(self.slot) >= self.min_update_interval_slots
+    }
+
+    /// Update random oracle and start a new round.
+    pub fn update(&mut self, value: [u8; 32], slot: Slot, unix_timestamp: UnixTimestamp) {
         self.value = value;
         self.slot = slot;
+        self.unix_timestamp = unix_timestamp;
+        self.round += 1;
+    }
+
+    /// Reveal committed submission and publish a value derived from it, previous value
+    /// and the slot hash of the commitment slot.
+    pub fn mix(
+        &mut self,
+        submission: [u8; 32],
+        slot_hash: [u8; 32],
+        slot: Slot,
+        unix_timestamp: UnixTimestamp,
+    ) {
+        let value = hashv(&[&submission, &self.value, &slot_hash]).to_bytes();
+
+        self.reveal(value, slot, unix_timestamp);
+        self.submission = submission;
+        self.slot_hash = slot_hash;
+    }
+
+    /// Reveal committed signature and publish a value derived from it and the slot hash
+    /// of the commitment slot.
+    pub fn reveal_signature(
+        &mut self,
+        signature: &[u8; 64],
+        slot_hash: [u8; 32],
+        slot: Slot,
+        unix_timestamp: UnixTimestamp,
+    ) {
+        let value = hashv(&[signature, &slot_hash]).to_bytes();
+
+        self.reveal(value, slot, unix_timestamp);
+        self.slot_hash = slot_hash;
+    }
+
+    /// Message to be signed by authority for a signature based update.
+    ///
+    /// Consists of randomness oracle pubkey, last slot and previous value.
+    pub fn signature_message(&self, randomness_oracle: &Pubkey) -> Vec<u8> {
+        let mut message = Vec::with_capacity(72);
+        message.extend_from_slice(randomness_oracle.as_ref());
+        message.extend_from_slice(&self.slot.to_le_bytes());
+        message.extend_from_slice(&self.value);
+        message
+    }
+
+    /// Commit to the hash of a future random value.
+    pub fn commit(&mut self, hash: [u8; 32], slot: Slot) {
+        self.commitment = hash;
+        self.commit_slot = slot;
+    }
+
+    /// Check whether there is a commitment waiting for reveal.
+    pub fn has_commitment(&self) -> bool {
+        self.commitment != [0u8; 32]
+    }
+
+    /// Check revealed data against the pending commitment.
+    pub fn check_reveal(&self, hash: [u8; 32], slot: Slot) -> ProgramResult {
+        if !self.has_commitment() {
+            return Err(RandomnessOracleError::NoPendingCommitment.into());
+        }
+
+        // Reveal is only allowed after the slot of commitment
+        if slot <= self.commit_slot {
+            return Err(RandomnessOracleError::RevealTooEarly.into());
+        }
+
+        if hash != self.commitment {
+            return Err(RandomnessOracleError::CommitmentMismatch.into());
+        }
+
+        Ok(())
+    }
+
+    /// Publish revealed seed as a new random value and clear the commitment.
+    pub fn reveal(&mut self, seed: [u8; 32], slot: Slot, unix_timestamp: UnixTimestamp) {
+        self.update(seed, slot, unix_timestamp);
+        self.commitment = [0u8; 32];
+        self.commit_slot = 0;
+    }
+
+    /// Check whether the pending commitment was not revealed in time.
+    pub fn is_reveal_expired(&self, slot: Slot) -> bool {
+        self.has_commitment() && slot > self.commit_slot.saturating_add(MAX_REVEAL_DELAY_SLOTS)
+    }
+
+    /// Drop the expired commitment, so the operator is slashed only once for it.
+    pub fn slash(&mut self) {
+        self.commitment = [0u8; 32];
+        self.commit_slot = 0;
+    }
+
+    /// Propose a new authority, default pubkey cancels the proposal.
+    pub fn propose_authority(&mut self, new_authority: Pubkey) {
+        self.pending_authority = new_authority;
+    }
+
+    /// Check whether the given key is the proposed authority.
+    pub fn is_pending_authority(&self, authority: &Pubkey) -> bool {
+        self.pending_authority != Pubkey::default() && self.pending_authority == *authority
+    }
+
+    /// Make the proposed authority the current one.
+    ///
+    /// Approval is dropped, the new authority has to be vetted again.
+    pub fn accept_authority(&mut self) {
+        self.authority = self.pending_authority;
+        self.pending_authority = Pubkey::default();
+        self.approved = false;
+    }
+
+    /// History account if history is kept.
+    pub fn history_address(&self) -> Option<Pubkey> {
+        if self.history == Pubkey::default() {
+            None
+        } else {
+            Some(self.history)
+        }
+    }
+
+    /// Check whether random oracle has the current layout version.
+    pub fn is_current_version(&self) -> bool {
+        self.version == RANDOMNESS_ORACLE_VERSION
+    }
+
+    /// Upgrade random oracle to the current layout version.
+    pub fn migrate(&mut self) {
+        self.version = RANDOMNESS_ORACLE_VERSION;
+    }
+
+    /// Check whether data length matches any supported layout version.
+    pub fn is_supported_len(len: usize) -> bool {
+        len == Self::LEN || len == RandomnessOracleV1::LEN
+    }
+
+    /// Check whether random oracle was closed.
+    pub fn is_closed(&self) -> bool {
+        self.account_type == AccountType::ClosedRandomnessOracle
+    }
+
+    /// Close random oracle, all data is zeroed except of closed account type.
+    pub fn close(&mut self) {
+        *self = Self {
+            account_type: AccountType::ClosedRandomnessOracle,
+            ..Default::default()
+        };
     }
 }
 
 impl Sealed for RandomnessOracle {}
 
 impl Pack for RandomnessOracle {
-    // 1 + 32 + 32 + 8
-    const LEN: usize = 73;
+    // 1 + 1 + 32 + 32 + 8 + 8 + 32 + 8 + 32 + 32 + 32 + 32 + 8 + 8 + 32 + 128 + 8 + 8 + 1 + 1
+    // + 1 + 1 + RANDOMNESS_ORACLE_RESERVED_LEN
+    const LEN: usize = 572;
+
+    fn unpack_unchecked(input: &[u8]) -> Result<Self, ProgramError> {
+        // Legacy accounts are readable until migrated
+        if input.len() == RandomnessOracleV1::LEN {
+            return RandomnessOracleV1::unpack_from_slice(input).map(Self::from);
+        }
+
+        if input.len() != Self::LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mut randomness_oracle = Self::unpack_from_slice(input)?;
+
+        // Newer layout could lose its fields on write
+        if randomness_oracle.version > RANDOMNESS_ORACLE_VERSION {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        // Upgrade in place
+        if randomness_oracle.is_initialized() && !randomness_oracle.is_current_version() {
+            randomness_oracle.migrate();
+        }
+
+        Ok(randomness_oracle)
+    }
 
     fn pack_into_slice(&self, dst: &mut [u8]) {
         let mut slice = dst;
-        self.serialize(&mut slice).unwrap()
+        self.serialize(&mut slice).unwrap();
+
+        // Keep reserved bytes zeroed
+        for byte in slice.iter_mut() {
+            *byte = 0;
+        }
     }
 
     fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
-        Self::try_from_slice(src).map_err(|_| {
-            msg!("Failed to deserialize");
-            msg!("Actual LEN: {}", std::mem::size_of::<RandomnessOracle>());
-            ProgramError::InvalidAccountData
+        // Reserved bytes are left unread
+        Self::deserialize(&mut &src[..]).map_err(|_| {
+            msg!("Expected LEN: {}, actual LEN: {}", Self::LEN, src.len());
+            RandomnessOracleError::DeserializationFailed.into()
         })
     }
 }
@@ -74,3 +428,56 @@ impl IsInitialized for RandomnessOracle {
         self.account_type == AccountType::RandomnessOracle
     }
 }
+
+/// Zero padded bytes as string.
+fn unpad(bytes: &[u8]) -> String {
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+/// Random oracle layout version 1, the account has no version field.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct RandomnessOracleV1 {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Authority.
+    pub authority: Pubkey,
+    /// Random value.
+    pub value: [u8; 32],
+    /// Last slot.
+    pub slot: Slot,
+}
+
+impl RandomnessOracleV1 {
+    /// Size of version 1 layout.
+    // 1 + 32 + 32 + 8
+    pub const LEN: usize = 73;
+
+    /// Unpack version 1 layout.
+    pub fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Expected LEN: {}, actual LEN: {}", Self::LEN, src.len());
+            RandomnessOracleError::DeserializationFailed.into()
+        })
+    }
+
+    /// Pack version 1 layout.
+    pub fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+}
+
+impl From<RandomnessOracleV1> for RandomnessOracle {
+    fn from(randomness_oracle: RandomnessOracleV1) -> Self {
+        Self {
+            account_type: randomness_oracle.account_type,
+            version: 1,
+            authority: randomness_oracle.authority,
+            value: randomness_oracle.value,
+            slot: randomness_oracle.slot,
+            ..Default::default()
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Random oracle history state definitions.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    clock::Slot,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::AccountType;
+use crate::error::RandomnessOracleError;
+
+/// Number of entries kept in random oracle history.
+pub const HISTORY_LEN: usize = 64;
+
+/// Random oracle history initialization params.
+pub struct InitRandomnessOracleHistoryParams {
+    /// Random oracle.
+    pub randomness_oracle: Pubkey,
+}
+
+/// Random value published at slot.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct HistoryEntry {
+    /// Slot.
+    pub slot: Slot,
+    /// Round.
+    pub round: u64,
+    /// Random value.
+    pub value: [u8; 32],
+}
+
+/// Ring buffer of the latest random oracle values.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq)]
+pub struct RandomnessOracleHistory {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Random oracle.
+    pub randomness_oracle: Pubkey,
+    /// Total number of pushed entries.
+    pub count: u64,
+    /// Entries, `count % HISTORY_LEN` is the index of the next entry to write.
+    pub entries: [HistoryEntry; HISTORY_LEN],
+}
+
+impl Default for RandomnessOracleHistory {
+    fn default() -> Self {
+        Self {
+            account_type: AccountType::default(),
+            randomness_oracle: Pubkey::default(),
+            count: 0,
+            entries: [HistoryEntry::default(); HISTORY_LEN],
+        }
+    }
+}
+
+impl RandomnessOracleHistory {
+    /// Initialize a random oracle history.
+    pub fn init(&mut self, params: InitRandomnessOracleHistoryParams) {
+        self.account_type = AccountType::RandomnessOracleHistory;
+        self.randomness_oracle = params.randomness_oracle;
+        self.count = 0;
+        self.entries = [HistoryEntry::default(); HISTORY_LEN];
+    }
+
+    /// Push a new entry, overwriting the oldest one.
+    pub fn push(&mut self, slot: Slot, round: u64, value: [u8; 32]) {
+        let index = (self.count % HISTORY_LEN as u64) as usize;
+        self.entries[index] = HistoryEntry { slot, round, value };
+        self.count += 1;
+    }
+
+    /// Iterate over kept entries from the oldest to the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &HistoryEntry> {
+        let len = self.count.min(HISTORY_LEN as u64) as usize;
+        let start = if self.count > HISTORY_LEN as u64 {
+            (self.count % HISTORY_LEN as u64) as usize
+        } else {
+            0
+        };
+
+        (0..len).map(move |i| &self.entries[(start + i) % HISTORY_LEN])
+    }
+
+    /// Find the first entry published at or after the slot.
+    ///
+    /// Fails if the entry preceding it is not kept anymore, since an earlier
+    /// value at or after the slot could have been overwritten.
+    pub fn find_at_slot(&self, slot: Slot) -> Result<HistoryEntry, RandomnessOracleError> {
+        let mut previous: Option<&HistoryEntry> = None;
+
+        for entry in self.iter() {
+            if entry.slot >= slot {
+                return match previous {
+                    Some(_) => Ok(*entry),
+                    None => Err(RandomnessOracleError::HistoryExpired),
+                };
+            }
+            previous = Some(entry);
+        }
+
+        Err(RandomnessOracleError::ValueNotPublished)
+    }
+}
+
+impl Sealed for RandomnessOracleHistory {}
+
+impl Pack for RandomnessOracleHistory {
+    // 1 + 32 + 8 + (8 + 8 + 32) * 64
+    const LEN: usize = 3113;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Expected LEN: {}, actual LEN: {}", Self::LEN, src.len());
+            RandomnessOracleError::DeserializationFailed.into()
+        })
+    }
+}
+
+impl IsInitialized for RandomnessOracleHistory {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RandomnessOracleHistory
+    }
+}
@@ -0,0 +1,116 @@
+//! Random oracle registry state definitions.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::AccountType;
+use crate::error::RandomnessOracleError;
+
+/// Max number of random oracles registered for an authority.
+///
+/// Registry is a fixed size account, an authority can't have more random oracles
+/// until one of them is closed or moved to another authority.
+pub const MAX_REGISTRY_ORACLES: usize = 64;
+
+/// Random oracle registry initialization params.
+pub struct InitRandomnessOracleRegistryParams {
+    /// Authority.
+    pub authority: Pubkey,
+}
+
+/// Random oracles of an authority, lets clients enumerate them with a single account read.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq)]
+pub struct RandomnessOracleRegistry {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Authority.
+    pub authority: Pubkey,
+    /// Number of registered random oracles.
+    pub oracles_len: u8,
+    /// Registered random oracles.
+    pub oracles: [Pubkey; MAX_REGISTRY_ORACLES],
+}
+
+impl Default for RandomnessOracleRegistry {
+    fn default() -> Self {
+        Self {
+            account_type: AccountType::default(),
+            authority: Pubkey::default(),
+            oracles_len: 0,
+            oracles: [Pubkey::default(); MAX_REGISTRY_ORACLES],
+        }
+    }
+}
+
+impl RandomnessOracleRegistry {
+    /// Initialize a random oracle registry.
+    pub fn init(&mut self, params: InitRandomnessOracleRegistryParams) {
+        self.account_type = AccountType::RandomnessOracleRegistry;
+        self.authority = params.authority;
+        self.oracles_len = 0;
+        self.oracles = [Pubkey::default(); MAX_REGISTRY_ORACLES];
+    }
+
+    /// Registered random oracles.
+    pub fn oracles(&self) -> &[Pubkey] {
+        &self.oracles[..self.oracles_len as usize]
+    }
+
+    /// Register random oracle.
+    pub fn add(&mut self, randomness_oracle: Pubkey) -> Result<(), ProgramError> {
+        if self.oracles().contains(&randomness_oracle) {
+            return Ok(());
+        }
+
+        let len = self.oracles_len as usize;
+        if len == MAX_REGISTRY_ORACLES {
+            return Err(RandomnessOracleError::RegistryFull.into());
+        }
+
+        self.oracles[len] = randomness_oracle;
+        self.oracles_len += 1;
+
+        Ok(())
+    }
+
+    /// Unregister random oracle, the last one takes its place.
+    pub fn remove(&mut self, randomness_oracle: &Pubkey) {
+        if let Some(index) = self.oracles().iter().position(|o| o == randomness_oracle) {
+            let last = self.oracles_len as usize - 1;
+            self.oracles[index] = self.oracles[last];
+            self.oracles[last] = Pubkey::default();
+            self.oracles_len -= 1;
+        }
+    }
+}
+
+impl Sealed for RandomnessOracleRegistry {}
+
+impl Pack for RandomnessOracleRegistry {
+    // 1 + 32 + 1 + 32 * 64
+    const LEN: usize = 2082;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Expected LEN: {}, actual LEN: {}", Self::LEN, src.len());
+            RandomnessOracleError::DeserializationFailed.into()
+        })
+    }
+}
+
+impl IsInitialized for RandomnessOracleRegistry {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RandomnessOracleRegistry
+    }
+}
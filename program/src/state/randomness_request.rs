@@ -0,0 +1,177 @@
+//! Randomness request state definitions.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    clock::Slot,
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::AccountType;
+use crate::error::RandomnessOracleError;
+
+/// Maximum number of accounts passed to the callback program.
+pub const MAX_CALLBACK_ACCOUNTS: usize = 8;
+
+/// Maximum length of the callback instruction data.
+pub const MAX_CALLBACK_DATA_LEN: usize = 32;
+
+/// Number of slots after the request it can be cancelled, if not fulfilled.
+pub const REQUEST_EXPIRY_SLOTS: u64 = 300;
+
+/// Randomness request initialization params.
+pub struct InitRandomnessRequestParams {
+    /// Random oracle.
+    pub randomness_oracle: Pubkey,
+    /// Requester.
+    pub requester: Pubkey,
+    /// Request seed.
+    pub seed: [u8; 32],
+    /// Callback program.
+    pub callback_program: Pubkey,
+    /// Callback accounts.
+    pub callback_accounts: Vec<CallbackAccount>,
+    /// Callback instruction data.
+    pub callback_data: Vec<u8>,
+    /// Request slot.
+    pub slot: Slot,
+    /// Subscription paying the request fee on fulfillment.
+    pub subscription: Option<Pubkey>,
+    /// Request fee.
+    pub fee: u64,
+}
+
+/// Account passed to the callback program.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct CallbackAccount {
+    /// Account.
+    pub pubkey: Pubkey,
+    /// Is account writable.
+    pub is_writable: bool,
+}
+
+/// Request for a random value delivered to the callback program.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct RandomnessRequest {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Random oracle.
+    pub randomness_oracle: Pubkey,
+    /// Requester.
+    pub requester: Pubkey,
+    /// Is request fulfilled.
+    pub fulfilled: bool,
+    /// Request seed.
+    pub seed: [u8; 32],
+    /// Slot of the request.
+    pub slot: Slot,
+    /// Callback program, `Pubkey::default()` if there is no callback.
+    pub callback_program: Pubkey,
+    /// Number of used callback accounts.
+    pub callback_accounts_len: u8,
+    /// Callback accounts.
+    pub callback_accounts: [CallbackAccount; MAX_CALLBACK_ACCOUNTS],
+    /// Length of the callback instruction data.
+    pub callback_data_len: u8,
+    /// Callback instruction data.
+    pub callback_data: [u8; MAX_CALLBACK_DATA_LEN],
+    /// Random value, hash of the request slot hash, seed and request address.
+    pub value: [u8; 32],
+    /// Slot of the fulfillment.
+    pub fulfilled_slot: Slot,
+    /// Subscription paying the request fee, `Pubkey::default()` if the fee was paid upfront.
+    pub subscription: Pubkey,
+    /// Request fee reserved in the subscription, paid on fulfillment.
+    pub fee: u64,
+}
+
+impl RandomnessRequest {
+    /// Initialize a randomness request.
+    pub fn init(&mut self, params: InitRandomnessRequestParams) {
+        self.account_type = AccountType::RandomnessRequest;
+        self.randomness_oracle = params.randomness_oracle;
+        self.requester = params.requester;
+        self.fulfilled = false;
+        self.seed = params.seed;
+        self.slot = params.slot;
+        self.callback_program = params.callback_program;
+        self.callback_accounts_len = params.callback_accounts.len() as u8;
+        self.callback_accounts = [CallbackAccount::default(); MAX_CALLBACK_ACCOUNTS];
+        self.callback_accounts[..params.callback_accounts.len()]
+            .copy_from_slice(&params.callback_accounts);
+        self.callback_data_len = params.callback_data.len() as u8;
+        self.callback_data = [0; MAX_CALLBACK_DATA_LEN];
+        self.callback_data[..params.callback_data.len()].copy_from_slice(&params.callback_data);
+        self.subscription = params.subscription.unwrap_or_default();
+        self.fee = params.fee;
+    }
+
+    /// Callback program, if any.
+    pub fn callback_program(&self) -> Option<Pubkey> {
+        if self.callback_program == Pubkey::default() {
+            None
+        } else {
+            Some(self.callback_program)
+        }
+    }
+
+    /// Subscription paying the request fee, if any.
+    pub fn subscription(&self) -> Option<Pubkey> {
+        if self.subscription == Pubkey::default() {
+            None
+        } else {
+            Some(self.subscription)
+        }
+    }
+
+    /// Used callback accounts.
+    pub fn callback_accounts(&self) -> &[CallbackAccount] {
+        &self.callback_accounts[..self.callback_accounts_len as usize]
+    }
+
+    /// Used callback instruction data.
+    pub fn callback_data(&self) -> &[u8] {
+        &self.callback_data[..self.callback_data_len as usize]
+    }
+
+    /// Check whether unfulfilled request can be cancelled at the given slot.
+    pub fn is_expired(&self, slot: Slot) -> bool {
+        !self.fulfilled && slot.saturating_sub(self.slot) > REQUEST_EXPIRY_SLOTS
+    }
+
+    /// Fulfill request with the random value.
+    pub fn fulfill(&mut self, value: [u8; 32], slot: Slot) {
+        self.fulfilled = true;
+        self.value = value;
+        self.fulfilled_slot = slot;
+    }
+}
+
+impl Sealed for RandomnessRequest {}
+
+impl Pack for RandomnessRequest {
+    // 1 + 32 + 32 + 1 + 32 + 8 + 32 + 1 + (32 + 1) * 8 + 1 + 32 + 32 + 8 + 32 + 8
+    const LEN: usize = 516;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Expected LEN: {}, actual LEN: {}", Self::LEN, src.len());
+            RandomnessOracleError::DeserializationFailed.into()
+        })
+    }
+}
+
+impl IsInitialized for RandomnessRequest {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RandomnessRequest
+    }
+}
@@ -0,0 +1,110 @@
+//! Randomness request queue state definitions.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::AccountType;
+use crate::error::RandomnessOracleError;
+
+/// Max number of pending randomness requests queued for a randomness oracle.
+pub const MAX_QUEUE_REQUESTS: usize = 64;
+
+/// Randomness request queue initialization params.
+pub struct InitRandomnessRequestQueueParams {
+    /// Random oracle.
+    pub randomness_oracle: Pubkey,
+}
+
+/// Pending randomness requests of a random oracle, lets the authority find them
+/// with a single account read.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq)]
+pub struct RandomnessRequestQueue {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Random oracle.
+    pub randomness_oracle: Pubkey,
+    /// Number of pending randomness requests.
+    pub requests_len: u8,
+    /// Pending randomness requests.
+    pub requests: [Pubkey; MAX_QUEUE_REQUESTS],
+}
+
+impl Default for RandomnessRequestQueue {
+    fn default() -> Self {
+        Self {
+            account_type: AccountType::default(),
+            randomness_oracle: Pubkey::default(),
+            requests_len: 0,
+            requests: [Pubkey::default(); MAX_QUEUE_REQUESTS],
+        }
+    }
+}
+
+impl RandomnessRequestQueue {
+    /// Initialize a randomness request queue.
+    pub fn init(&mut self, params: InitRandomnessRequestQueueParams) {
+        self.account_type = AccountType::RandomnessRequestQueue;
+        self.randomness_oracle = params.randomness_oracle;
+        self.requests_len = 0;
+        self.requests = [Pubkey::default(); MAX_QUEUE_REQUESTS];
+    }
+
+    /// Pending randomness requests.
+    pub fn requests(&self) -> &[Pubkey] {
+        &self.requests[..self.requests_len as usize]
+    }
+
+    /// Queue randomness request.
+    pub fn push(&mut self, randomness_request: Pubkey) -> Result<(), ProgramError> {
+        let len = self.requests_len as usize;
+        if len == MAX_QUEUE_REQUESTS {
+            return Err(RandomnessOracleError::RequestQueueFull.into());
+        }
+
+        self.requests[len] = randomness_request;
+        self.requests_len += 1;
+
+        Ok(())
+    }
+
+    /// Remove randomness request from the queue, the last one takes its place.
+    pub fn remove(&mut self, randomness_request: &Pubkey) {
+        if let Some(index) = self.requests().iter().position(|r| r == randomness_request) {
+            let last = self.requests_len as usize - 1;
+            self.requests[index] = self.requests[last];
+            self.requests[last] = Pubkey::default();
+            self.requests_len -= 1;
+        }
+    }
+}
+
+impl Sealed for RandomnessRequestQueue {}
+
+impl Pack for RandomnessRequestQueue {
+    // 1 + 32 + 1 + 32 * 64
+    const LEN: usize = 2082;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Expected LEN: {}, actual LEN: {}", Self::LEN, src.len());
+            RandomnessOracleError::DeserializationFailed.into()
+        })
+    }
+}
+
+impl IsInitialized for RandomnessRequestQueue {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::RandomnessRequestQueue
+    }
+}
@@ -0,0 +1,122 @@
+//! Subscription state definitions.
+
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
+use solana_program::{
+    msg,
+    program_error::ProgramError,
+    program_pack::{IsInitialized, Pack, Sealed},
+    pubkey::Pubkey,
+};
+
+use super::AccountType;
+use crate::error::RandomnessOracleError;
+
+/// Subscription initialization params.
+pub struct InitSubscriptionParams {
+    /// Random oracle.
+    pub randomness_oracle: Pubkey,
+    /// Owner.
+    pub owner: Pubkey,
+}
+
+/// Prepaid balance reserved for each randomness request of the owner and paid on fulfillment.
+#[repr(C)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, BorshSchema, PartialEq, Default)]
+pub struct Subscription {
+    /// Account type.
+    pub account_type: AccountType,
+    /// Random oracle.
+    pub randomness_oracle: Pubkey,
+    /// Owner, the requester allowed to use the subscription.
+    pub owner: Pubkey,
+    /// Lamports available for request fees, on top of the rent exempt reserve
+    /// and the fees reserved for pending requests.
+    pub balance: u64,
+    /// Number of paid requests.
+    pub request_count: u64,
+    /// Number of pending requests with the reserved fee.
+    pub pending_requests: u64,
+}
+
+impl Subscription {
+    /// Initialize a subscription.
+    pub fn init(&mut self, params: InitSubscriptionParams) {
+        self.account_type = AccountType::Subscription;
+        self.randomness_oracle = params.randomness_oracle;
+        self.owner = params.owner;
+        self.balance = 0;
+        self.request_count = 0;
+        self.pending_requests = 0;
+    }
+
+    /// Add funded lamports to the balance.
+    pub fn fund(&mut self, amount: u64) -> Result<(), ProgramError> {
+        self.balance = self
+            .balance
+            .checked_add(amount)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        Ok(())
+    }
+
+    /// Reserve request fee from the balance until the request is fulfilled or cancelled.
+    pub fn reserve(&mut self, fee: u64) -> Result<(), ProgramError> {
+        self.balance = self
+            .balance
+            .checked_sub(fee)
+            .ok_or(RandomnessOracleError::InsufficientSubscriptionBalance)?;
+        self.pending_requests += 1;
+
+        Ok(())
+    }
+
+    /// Settle reserved fee of the fulfilled request.
+    pub fn settle(&mut self) -> Result<(), ProgramError> {
+        self.pending_requests = self
+            .pending_requests
+            .checked_sub(1)
+            .ok_or(ProgramError::InvalidArgument)?;
+        self.request_count += 1;
+
+        Ok(())
+    }
+
+    /// Return reserved fee of the cancelled request to the balance.
+    pub fn refund(&mut self, fee: u64) -> Result<(), ProgramError> {
+        self.pending_requests = self
+            .pending_requests
+            .checked_sub(1)
+            .ok_or(ProgramError::InvalidArgument)?;
+        self.balance = self
+            .balance
+            .checked_add(fee)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        Ok(())
+    }
+}
+
+impl Sealed for Subscription {}
+
+impl Pack for Subscription {
+    // 1 + 32 + 32 + 8 + 8 + 8
+    const LEN: usize = 89;
+
+    fn pack_into_slice(&self, dst: &mut [u8]) {
+        let mut slice = dst;
+        self.serialize(&mut slice).unwrap()
+    }
+
+    fn unpack_from_slice(src: &[u8]) -> Result<Self, ProgramError> {
+        Self::try_from_slice(src).map_err(|_| {
+            msg!("Expected LEN: {}, actual LEN: {}", Self::LEN, src.len());
+            RandomnessOracleError::DeserializationFailed.into()
+        })
+    }
+}
+
+impl IsInitialized for Subscription {
+    fn is_initialized(&self) -> bool {
+        self.account_type == AccountType::Subscription
+    }
+}
@@ -1,7 +1,24 @@
+use crate::error::RandomnessOracleError;
 use solana_program::{
-    account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
+    account_info::AccountInfo,
+    bpf_loader_upgradeable,
+    clock::Slot,
+    entrypoint::ProgramResult,
+    hash::Hash,
+    instruction::Instruction,
+    msg,
+    program::{invoke, invoke_signed},
+    program_error::ProgramError,
+    program_pack::IsInitialized,
     pubkey::Pubkey,
+    rent::Rent,
+    system_instruction,
+    sysvar::{
+        self,
+        instructions::{load_current_index, load_instruction_at},
+    },
 };
+use std::convert::TryInto;
 
 /// Assert signer.
 pub fn assert_signer(account: &AccountInfo) -> ProgramResult {
@@ -20,3 +37,172 @@ pub fn assert_owned_by(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
         Ok(())
     }
 }
+
+/// Assert rent exempt.
+pub fn assert_rent_exempt(rent: &Rent, account_info: &AccountInfo) -> ProgramResult {
+    if !rent.is_exempt(account_info.lamports(), account_info.data_len()) {
+        msg!(&rent.minimum_balance(account_info.data_len()).to_string());
+        Err(RandomnessOracleError::NotRentExempt.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Assert uninitialized.
+pub fn assert_uninitialized<T: IsInitialized>(account: &T) -> ProgramResult {
+    if account.is_initialized() {
+        Err(RandomnessOracleError::AlreadyInitialized.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Assert account data length.
+pub fn assert_data_len(account_info: &AccountInfo, len: usize) -> ProgramResult {
+    if account_info.data_len() != len {
+        Err(RandomnessOracleError::WrongAccountSize.into())
+    } else {
+        Ok(())
+    }
+}
+
+/// Read the hash of the given slot from the `SlotHashes` sysvar.
+///
+/// Only the most recent 512 slots are kept, current slot hash is added in the next slot.
+/// Reads raw account data instead of deserializing the whole sysvar to save compute units.
+pub fn get_slot_hash(slot_hashes_info: &AccountInfo, slot: Slot) -> Result<Hash, ProgramError> {
+    if !sysvar::slot_hashes::check_id(slot_hashes_info.key) {
+        return Err(RandomnessOracleError::InvalidSysvar.into());
+    }
+
+    let data = slot_hashes_info.data.borrow();
+
+    let len = u64::from_le_bytes(
+        data.get(0..8)
+            .ok_or(ProgramError::InvalidAccountData)?
+            .try_into()
+            .unwrap(),
+    );
+
+    // 8 bytes of entries count followed by (slot, hash) entries, most recent first
+    for entry in data[8..].chunks_exact(40).take(len as usize) {
+        let entry_slot = u64::from_le_bytes(entry[0..8].try_into().unwrap());
+        if entry_slot == slot {
+            return Ok(Hash::new(&entry[8..40]));
+        }
+        if entry_slot < slot {
+            break;
+        }
+    }
+
+    Err(RandomnessOracleError::SlotHashNotFound.into())
+}
+
+/// Read upgrade authority of the program from its program data account.
+///
+/// Reads raw account data to avoid pulling in bincode for `UpgradeableLoaderState`.
+pub fn get_upgrade_authority(
+    program_id: &Pubkey,
+    program_data_info: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    let (program_data_key, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    if *program_data_info.key != program_data_key
+        || *program_data_info.owner != bpf_loader_upgradeable::id()
+    {
+        return Err(RandomnessOracleError::InvalidProgramData.into());
+    }
+
+    let data = program_data_info.data.borrow();
+
+    // 4 bytes of `ProgramData` variant index, 8 bytes of slot and optional upgrade authority
+    let header = data.get(0..45).ok_or(ProgramError::InvalidAccountData)?;
+    if u32::from_le_bytes(header[0..4].try_into().unwrap()) != 3 {
+        return Err(RandomnessOracleError::InvalidProgramData.into());
+    }
+
+    if header[12] == 0 {
+        return Ok(None);
+    }
+
+    Ok(Some(Pubkey::new(&header[13..45])))
+}
+
+/// Load the instruction preceding the current one from the `Instructions` sysvar.
+pub fn load_previous_instruction(
+    instructions_info: &AccountInfo,
+) -> Result<(Instruction, u16), ProgramError> {
+    if !sysvar::instructions::check_id(instructions_info.key) {
+        return Err(RandomnessOracleError::InvalidSysvar.into());
+    }
+
+    let data = instructions_info.data.borrow();
+    let current_index = load_current_index(&data);
+    if current_index == 0 {
+        return Err(RandomnessOracleError::InvalidSignatureVerification.into());
+    }
+
+    let index = current_index - 1;
+    let instruction = load_instruction_at(index as usize, &data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    Ok((instruction, index))
+}
+
+/// Create rent exempt account at program derived address.
+///
+/// Lamports transferred to the address beforehand are topped up instead of
+/// failing `create_account`, so the address can't be blocked by a transfer.
+pub fn create_program_derived_account<'a>(
+    payer_info: &AccountInfo<'a>,
+    account_info: &AccountInfo<'a>,
+    system_program_info: &AccountInfo<'a>,
+    rent: &Rent,
+    space: usize,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let required_lamports = rent
+        .minimum_balance(space)
+        .saturating_sub(account_info.lamports());
+
+    if account_info.lamports() == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(
+                payer_info.key,
+                account_info.key,
+                required_lamports,
+                space as u64,
+                owner,
+            ),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[signer_seeds],
+        );
+    }
+
+    if required_lamports > 0 {
+        invoke(
+            &system_instruction::transfer(payer_info.key, account_info.key, required_lamports),
+            &[
+                payer_info.clone(),
+                account_info.clone(),
+                system_program_info.clone(),
+            ],
+        )?;
+    }
+
+    invoke_signed(
+        &system_instruction::allocate(account_info.key, space as u64),
+        &[account_info.clone(), system_program_info.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account_info.key, owner),
+        &[account_info.clone(), system_program_info.clone()],
+        &[signer_seeds],
+    )
+}
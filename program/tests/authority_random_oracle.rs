@@ -0,0 +1,115 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError, find_registry_address, id, state::RandomnessOracleRegistry,
+};
+use solana_program::pubkey::Pubkey;
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use utils::*;
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    (context, test_randomness_oracle)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let new_authority = Keypair::new();
+
+    // New authority pays for its registry
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &new_authority.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    test_randomness_oracle
+        .propose_authority(&mut context, &new_authority.pubkey())
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.authority, context.payer.pubkey());
+    assert_eq!(randomness_oracle.pending_authority, new_authority.pubkey());
+
+    test_randomness_oracle
+        .accept_authority(&mut context, &new_authority)
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.authority, new_authority.pubkey());
+    assert_eq!(randomness_oracle.pending_authority, Pubkey::default());
+
+    // Randomness oracle is moved to the registry of the new authority
+    let (registry, _) = find_registry_address(&id(), &context.payer.pubkey());
+    let registry = get_account(&mut context, &registry).await;
+    let registry = RandomnessOracleRegistry::unpack(&registry.data).unwrap();
+    assert!(registry.oracles().is_empty());
+
+    let (new_registry, _) = find_registry_address(&id(), &new_authority.pubkey());
+    let new_registry = get_account(&mut context, &new_registry).await;
+    let new_registry = RandomnessOracleRegistry::unpack(&new_registry.data).unwrap();
+    assert_eq!(
+        new_registry.oracles(),
+        &[test_randomness_oracle.keypair.pubkey()]
+    );
+
+    // Previous authority can't update anymore
+    assert_eq!(
+        test_randomness_oracle
+            .update(&mut context, [1u8; 32])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+}
+
+#[tokio::test]
+async fn fail_accept_by_not_proposed_authority() {
+    let (mut context, test_randomness_oracle) = setup().await;
+
+    test_randomness_oracle
+        .propose_authority(&mut context, &Keypair::new().pubkey())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .accept_authority(&mut context, &Keypair::new())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidPendingAuthority)
+    );
+}
+
+#[tokio::test]
+async fn fail_accept_without_proposal() {
+    let (mut context, test_randomness_oracle) = setup().await;
+
+    assert_eq!(
+        test_randomness_oracle
+            .accept_authority(&mut context, &Keypair::new())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidPendingAuthority)
+    );
+}
@@ -0,0 +1,195 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    id, instruction,
+    state::{
+        RandomnessOracle, RandomnessOracleHistory, RandomnessOracleMetadata, UpdateMode,
+        MIN_BOND_LAMPORTS,
+    },
+};
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use utils::*;
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    (context, test_randomness_oracle)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let destination = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    test_randomness_oracle
+        .close(&mut context, &destination.pubkey())
+        .await
+        .unwrap();
+
+    let randomness_oracle_account = context
+        .banks_client
+        .get_account(test_randomness_oracle.keypair.pubkey())
+        .await
+        .unwrap();
+    assert!(randomness_oracle_account.is_none());
+
+    let destination_account = get_account(&mut context, &destination.pubkey()).await;
+    assert_eq!(
+        destination_account.lamports,
+        rent.minimum_balance(RandomnessOracle::LEN) + MIN_BOND_LAMPORTS
+    );
+}
+
+#[tokio::test]
+async fn success_with_history() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let destination = Keypair::new();
+    let history = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    test_randomness_oracle
+        .init_history(&mut context, &history)
+        .await
+        .unwrap();
+    test_randomness_oracle
+        .close(&mut context, &destination.pubkey())
+        .await
+        .unwrap();
+
+    let history_account = context
+        .banks_client
+        .get_account(history.pubkey())
+        .await
+        .unwrap();
+    assert!(history_account.is_none());
+
+    let destination_account = get_account(&mut context, &destination.pubkey()).await;
+    assert_eq!(
+        destination_account.lamports,
+        rent.minimum_balance(RandomnessOracle::LEN)
+            + rent.minimum_balance(RandomnessOracleHistory::LEN)
+            + MIN_BOND_LAMPORTS
+    );
+}
+
+#[tokio::test]
+async fn fail_without_history() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let history = Keypair::new();
+
+    test_randomness_oracle
+        .init_history(&mut context, &history)
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::close_randomness_oracle(
+            &id(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &context.payer.pubkey(),
+            &context.payer.pubkey(),
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        instruction_error(0, InstructionError::NotEnoughAccountKeys)
+    );
+}
+
+#[tokio::test]
+async fn fail_revive_in_same_transaction() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let randomness_oracle = test_randomness_oracle.keypair.pubkey();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            instruction::close_randomness_oracle(
+                &id(),
+                &randomness_oracle,
+                &context.payer.pubkey(),
+                &context.payer.pubkey(),
+                None,
+            ),
+            system_instruction::transfer(
+                &context.payer.pubkey(),
+                &randomness_oracle,
+                rent.minimum_balance(RandomnessOracle::LEN),
+            ),
+            instruction::init_randomness_oracle(
+                &id(),
+                &randomness_oracle,
+                &context.payer.pubkey(),
+                0,
+                RandomnessOracleMetadata::default(),
+                0,
+                UpdateMode::Direct,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(2, RandomnessOracleError::OracleClosed)
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_authority() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let fake_authority = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::close_randomness_oracle(
+            &id(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &fake_authority.pubkey(),
+            &fake_authority.pubkey(),
+            None,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_authority],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.authority, context.payer.pubkey());
+}
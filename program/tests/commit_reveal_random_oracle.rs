@@ -0,0 +1,193 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    state::{UpdateMode, MAX_REVEAL_DELAY_SLOTS},
+};
+use solana_program::hash::hash;
+use solana_program_test::*;
+use utils::*;
+
+const SEED: [u8; 32] = [7u8; 32];
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::with_mode(UpdateMode::CommitReveal);
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    (context, test_randomness_oracle)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let commitment = hash(&SEED).to_bytes();
+    test_randomness_oracle
+        .commit(&mut context, commitment)
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.commitment, commitment);
+    assert_eq!(randomness_oracle.commit_slot, 3);
+    assert_eq!(randomness_oracle.value, [0u8; 32]);
+
+    context.warp_to_slot(5).unwrap();
+
+    test_randomness_oracle
+        .reveal(&mut context, SEED)
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.value, SEED);
+    assert_eq!(randomness_oracle.slot, 5);
+    assert_eq!(randomness_oracle.commitment, [0u8; 32]);
+}
+
+#[tokio::test]
+async fn fail_reveal_wrong_seed() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&SEED).to_bytes())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .reveal(&mut context, [8u8; 32])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::CommitmentMismatch)
+    );
+}
+
+#[tokio::test]
+async fn fail_reveal_without_commitment() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .reveal(&mut context, SEED)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::NoPendingCommitment)
+    );
+}
+
+#[tokio::test]
+async fn fail_commit_twice() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&SEED).to_bytes())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .commit(&mut context, hash(&[8u8; 32]).to_bytes())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::CommitmentAlreadyPending)
+    );
+}
+
+#[tokio::test]
+async fn fail_update_with_commitment() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&SEED).to_bytes())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+
+    // Value can't be replaced by other update instructions before reveal
+    assert_eq!(
+        test_randomness_oracle
+            .update(&mut context, [8u8; 32])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidUpdateMode)
+    );
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.value, [0u8; 32]);
+    assert_eq!(randomness_oracle.commitment, hash(&SEED).to_bytes());
+}
+
+#[tokio::test]
+async fn fail_commit_direct_mode() {
+    let mut context = program_test().start_with_context().await;
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .commit(&mut context, hash(&SEED).to_bytes())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidUpdateMode)
+    );
+}
+
+#[tokio::test]
+async fn success_min_update_interval_above_reveal_delay() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let min_update_interval_slots = MAX_REVEAL_DELAY_SLOTS + 50;
+    test_randomness_oracle
+        .set_min_update_interval(&mut context, min_update_interval_slots)
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    let commit_slot = randomness_oracle.slot + min_update_interval_slots;
+
+    // Commit is rejected before the minimum update interval
+    context.warp_to_slot(commit_slot - 1).unwrap();
+    assert_eq!(
+        test_randomness_oracle
+            .commit(&mut context, hash(&SEED).to_bytes())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::UpdateTooEarly)
+    );
+
+    context.warp_to_slot(commit_slot).unwrap();
+    test_randomness_oracle
+        .commit(&mut context, hash(&SEED).to_bytes())
+        .await
+        .unwrap();
+
+    // Reveal within the reveal delay passes the minimum update interval
+    context.warp_to_slot(commit_slot + 2).unwrap();
+    test_randomness_oracle
+        .reveal(&mut context, SEED)
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.value, SEED);
+    assert_eq!(randomness_oracle.slot, commit_slot + 2);
+}
@@ -0,0 +1,189 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    find_randomness_oracle_address, id, instruction,
+    state::{
+        AccountType, RandomnessOracle, RandomnessOracleMetadata, UpdateMode, MIN_BOND_LAMPORTS,
+    },
+};
+use solana_program::{instruction::InstructionError, system_instruction::SystemError};
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack, pubkey::Pubkey, signer::Signer, system_instruction,
+    transaction::Transaction, transport,
+};
+use utils::*;
+
+const NAME: &str = "coin flip";
+const URI: &str = "https://example.com/coin-flip.json";
+
+async fn create(context: &mut ProgramTestContext, name: &str) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::create_randomness_oracle(
+            &id(),
+            &context.payer.pubkey(),
+            RandomnessOracleMetadata {
+                name: name.to_string(),
+                uri: URI.to_string(),
+                update_interval_slots: 10,
+            },
+            2,
+            MIN_BOND_LAMPORTS,
+            UpdateMode::Direct,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn get_randomness_oracle(
+    context: &mut ProgramTestContext,
+    pubkey: &Pubkey,
+) -> RandomnessOracle {
+    let account = get_account(context, pubkey).await;
+    assert_eq!(account.owner, id());
+
+    RandomnessOracle::unpack(&account.data).unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+
+    create(&mut context, NAME).await.unwrap();
+
+    let (randomness_oracle_pubkey, _) =
+        find_randomness_oracle_address(&id(), &context.payer.pubkey(), NAME);
+    let randomness_oracle = get_randomness_oracle(&mut context, &randomness_oracle_pubkey).await;
+
+    assert_eq!(
+        randomness_oracle.account_type,
+        AccountType::RandomnessOracle
+    );
+    assert_eq!(randomness_oracle.authority, context.payer.pubkey());
+    assert_eq!(randomness_oracle.min_update_interval_slots, 2);
+    assert_eq!(randomness_oracle.update_mode, UpdateMode::Direct);
+    assert!(randomness_oracle.derived);
+    assert_eq!(
+        randomness_oracle.metadata(),
+        RandomnessOracleMetadata {
+            name: NAME.to_string(),
+            uri: URI.to_string(),
+            update_interval_slots: 10,
+        }
+    );
+}
+
+#[tokio::test]
+async fn success_prefunded() {
+    let mut context = program_test().start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let (randomness_oracle_pubkey, _) =
+        find_randomness_oracle_address(&id(), &context.payer.pubkey(), NAME);
+
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &randomness_oracle_pubkey,
+            rent.minimum_balance(0),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    create(&mut context, NAME).await.unwrap();
+
+    let randomness_oracle = get_randomness_oracle(&mut context, &randomness_oracle_pubkey).await;
+    assert_eq!(randomness_oracle.authority, context.payer.pubkey());
+}
+
+#[tokio::test]
+async fn fail_already_created() {
+    let mut context = program_test().start_with_context().await;
+
+    create(&mut context, NAME).await.unwrap();
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        create(&mut context, NAME).await.unwrap_err().unwrap(),
+        instruction_error(
+            0,
+            InstructionError::Custom(SystemError::AccountAlreadyInUse as u32)
+        )
+    );
+}
+
+async fn update_metadata(
+    context: &mut ProgramTestContext,
+    metadata: RandomnessOracleMetadata,
+) -> transport::Result<()> {
+    let (randomness_oracle_pubkey, _) =
+        find_randomness_oracle_address(&id(), &context.payer.pubkey(), NAME);
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_metadata(
+            &id(),
+            &randomness_oracle_pubkey,
+            &context.payer.pubkey(),
+            metadata,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success_update_metadata_same_name() {
+    let mut context = program_test().start_with_context().await;
+
+    create(&mut context, NAME).await.unwrap();
+
+    let metadata = RandomnessOracleMetadata {
+        name: NAME.to_string(),
+        uri: "https://example.com/coin-flip-v2.json".to_string(),
+        update_interval_slots: 20,
+    };
+    update_metadata(&mut context, metadata.clone())
+        .await
+        .unwrap();
+
+    let (randomness_oracle_pubkey, _) =
+        find_randomness_oracle_address(&id(), &context.payer.pubkey(), NAME);
+    let randomness_oracle = get_randomness_oracle(&mut context, &randomness_oracle_pubkey).await;
+    assert_eq!(randomness_oracle.metadata(), metadata);
+}
+
+#[tokio::test]
+async fn fail_update_metadata_name() {
+    let mut context = program_test().start_with_context().await;
+
+    create(&mut context, NAME).await.unwrap();
+
+    assert_eq!(
+        update_metadata(
+            &mut context,
+            RandomnessOracleMetadata {
+                name: "dice roll".to_string(),
+                uri: URI.to_string(),
+                update_interval_slots: 10,
+            },
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        custom_error(0, RandomnessOracleError::ImmutableName)
+    );
+
+    let (randomness_oracle_pubkey, _) =
+        find_randomness_oracle_address(&id(), &context.payer.pubkey(), NAME);
+    let randomness_oracle = get_randomness_oracle(&mut context, &randomness_oracle_pubkey).await;
+    assert_eq!(randomness_oracle.metadata().name, NAME);
+}
@@ -0,0 +1,89 @@
+use randomness_oracle_program::{
+    event::{parse_events, RandomnessOracleEvent, RandomnessOracleEventKind},
+    id,
+};
+use solana_program::pubkey::Pubkey;
+
+fn event(kind: RandomnessOracleEventKind, round: u64) -> RandomnessOracleEvent {
+    RandomnessOracleEvent {
+        kind,
+        randomness_oracle: Pubkey::new_unique(),
+        round,
+        slot: 10 + round,
+        value: [round as u8; 32],
+        authority: Pubkey::new_unique(),
+    }
+}
+
+fn program_log(event: &RandomnessOracleEvent) -> String {
+    format!("Program log: {}", event.to_log_message())
+}
+
+#[test]
+fn success() {
+    let initialized = event(RandomnessOracleEventKind::Initialized, 0);
+    let updated = event(RandomnessOracleEventKind::Updated, 1);
+
+    let logs = vec![
+        format!("Program {} invoke [1]", id()),
+        "Program log: RandomnessOracleInstruction: InitRandomnessOracle".to_string(),
+        program_log(&initialized),
+        format!("Program {} consumed 20000 of 200000 compute units", id()),
+        format!("Program {} success", id()),
+        format!("Program {} invoke [1]", id()),
+        program_log(&updated),
+        format!("Program {} success", id()),
+    ];
+
+    assert_eq!(parse_events(&id(), &logs), vec![initialized, updated]);
+}
+
+#[test]
+fn success_after_cpi() {
+    let other_program = Pubkey::new_unique();
+    let closed = event(RandomnessOracleEventKind::Closed, 5);
+
+    let logs = vec![
+        format!("Program {} invoke [1]", id()),
+        "Program 11111111111111111111111111111111 invoke [2]".to_string(),
+        "Program 11111111111111111111111111111111 success".to_string(),
+        program_log(&closed),
+        format!("Program {} success", id()),
+        format!("Program {} invoke [1]", other_program),
+        format!(
+            "Program {} failed: custom program error: 0x1",
+            other_program
+        ),
+    ];
+
+    assert_eq!(parse_events(&id(), &logs), vec![closed]);
+}
+
+#[test]
+fn fail_logged_by_other_program() {
+    let other_program = Pubkey::new_unique();
+    let forged = event(RandomnessOracleEventKind::AuthorityChanged, 3);
+
+    let logs = vec![
+        format!("Program {} invoke [1]", other_program),
+        program_log(&forged),
+        format!("Program {} invoke [2]", id()),
+        format!("Program {} success", id()),
+        program_log(&forged),
+        format!("Program {} success", other_program),
+    ];
+
+    assert!(parse_events(&id(), &logs).is_empty());
+}
+
+#[test]
+fn fail_malformed_message() {
+    let logs = vec![
+        format!("Program {} invoke [1]", id()),
+        "Program log: RandomnessOracleEvent: not base64!".to_string(),
+        "Program log: RandomnessOracleEvent: AAAA".to_string(),
+        format!("Program {} success", id()),
+    ];
+
+    assert!(parse_events(&id(), &logs).is_empty());
+}
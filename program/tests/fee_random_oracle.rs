@@ -0,0 +1,247 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    find_bond_vault_address, find_request_queue_address, find_treasury_address, id, instruction,
+    state::{RandomnessOracle, RandomnessRequestQueue},
+};
+use solana_program::{instruction::InstructionError, system_instruction::SystemError};
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use utils::*;
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle, Keypair, u64) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    // Fee has to keep the treasury rent exempt
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let request_fee = rent.minimum_balance(0);
+    test_randomness_oracle
+        .set_request_fee(&mut context, request_fee)
+        .await
+        .unwrap();
+
+    // Requester pays for the request queue on the first request
+    let requester = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &requester.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (context, test_randomness_oracle, requester, request_fee)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle, requester, request_fee) = setup().await;
+    let (treasury, _) = find_treasury_address(&id(), &test_randomness_oracle.keypair.pubkey());
+
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &Keypair::new(),
+            &requester,
+            None,
+            [1u8; 32],
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        context.banks_client.get_balance(treasury).await.unwrap(),
+        request_fee
+    );
+
+    let destination = Pubkey::new_unique();
+    test_randomness_oracle
+        .withdraw_fees(&mut context, &destination)
+        .await
+        .unwrap();
+
+    assert_eq!(context.banks_client.get_balance(treasury).await.unwrap(), 0);
+    assert_eq!(
+        context.banks_client.get_balance(destination).await.unwrap(),
+        request_fee
+    );
+}
+
+#[tokio::test]
+async fn success_close() {
+    let (mut context, test_randomness_oracle, requester, request_fee) = setup().await;
+    let randomness_oracle = test_randomness_oracle.keypair.pubkey();
+    let (treasury, _) = find_treasury_address(&id(), &randomness_oracle);
+    let (request_queue, _) = find_request_queue_address(&id(), &randomness_oracle);
+    let (bond_vault, _) = find_bond_vault_address(&id(), &randomness_oracle);
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &randomness_request,
+            &requester,
+            None,
+            [1u8; 32],
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+    context.warp_to_slot(3).unwrap();
+    test_randomness_oracle
+        .fulfill(&mut context, &randomness_request.pubkey())
+        .await
+        .unwrap();
+
+    let bond = context.banks_client.get_balance(bond_vault).await.unwrap();
+    let destination = Pubkey::new_unique();
+    test_randomness_oracle
+        .close(&mut context, &destination)
+        .await
+        .unwrap();
+
+    // Collected fees and request queue rent are returned with the oracle
+    assert_eq!(context.banks_client.get_balance(treasury).await.unwrap(), 0);
+    assert!(context
+        .banks_client
+        .get_account(request_queue)
+        .await
+        .unwrap()
+        .is_none());
+    assert_eq!(
+        context.banks_client.get_balance(destination).await.unwrap(),
+        rent.minimum_balance(RandomnessOracle::LEN)
+            + rent.minimum_balance(RandomnessRequestQueue::LEN)
+            + request_fee
+            + bond
+    );
+}
+
+#[tokio::test]
+async fn fail_close_with_pending_request() {
+    let (mut context, test_randomness_oracle, requester, _) = setup().await;
+
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &Keypair::new(),
+            &requester,
+            None,
+            [1u8; 32],
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .close(&mut context, &Pubkey::new_unique())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::RequestQueueNotEmpty)
+    );
+}
+
+#[tokio::test]
+async fn fail_insufficient_fee_balance() {
+    let (mut context, test_randomness_oracle, requester, _) = setup().await;
+
+    // Request queue is created by the first request
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &Keypair::new(),
+            &requester,
+            None,
+            [1u8; 32],
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    // Requester without lamports can't pay the fee
+    assert_eq!(
+        test_randomness_oracle
+            .request(
+                &mut context,
+                &Keypair::new(),
+                &Keypair::new(),
+                None,
+                [1u8; 32],
+                None,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        instruction_error(
+            1,
+            InstructionError::Custom(SystemError::ResultWithNegativeLamports as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_withdraw_wrong_authority() {
+    let (mut context, test_randomness_oracle, requester, _) = setup().await;
+
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &Keypair::new(),
+            &requester,
+            None,
+            [1u8; 32],
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    let fake_authority = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::withdraw_fees(
+            &id(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &fake_authority.pubkey(),
+            &fake_authority.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_authority],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+}
@@ -0,0 +1,135 @@
+mod utils;
+
+use randomness_oracle_program::{error::RandomnessOracleError, id, instruction};
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use utils::*;
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle, Keypair) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    let history = Keypair::new();
+    test_randomness_oracle
+        .init_history(&mut context, &history)
+        .await
+        .unwrap();
+
+    (context, test_randomness_oracle, history)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle, history) = setup().await;
+
+    let initial = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(initial.history, history.pubkey());
+
+    context.warp_to_slot(3).unwrap();
+    test_randomness_oracle
+        .update(&mut context, [1u8; 32])
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+    test_randomness_oracle
+        .update(&mut context, [2u8; 32])
+        .await
+        .unwrap();
+
+    let randomness_oracle_history = test_randomness_oracle
+        .get_history(&mut context, &history.pubkey())
+        .await;
+    let entries: Vec<_> = randomness_oracle_history
+        .iter()
+        .map(|entry| (entry.slot, entry.round, entry.value))
+        .collect();
+
+    assert_eq!(
+        randomness_oracle_history.randomness_oracle,
+        test_randomness_oracle.keypair.pubkey()
+    );
+    assert_eq!(
+        entries,
+        vec![
+            (initial.slot, 0, initial.value),
+            (3, 1, [1u8; 32]),
+            (5, 2, [2u8; 32])
+        ]
+    );
+}
+
+#[tokio::test]
+async fn fail_already_set() {
+    let (mut context, test_randomness_oracle, _) = setup().await;
+
+    assert_eq!(
+        test_randomness_oracle
+            .init_history(&mut context, &Keypair::new())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::HistoryAlreadySet)
+    );
+}
+
+#[tokio::test]
+async fn fail_update_without_history() {
+    let (mut context, test_randomness_oracle, _) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_randomness_oracle(
+            &id(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &context.payer.pubkey(),
+            None,
+            [1u8; 32],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        instruction_error(0, InstructionError::NotEnoughAccountKeys)
+    );
+}
+
+#[tokio::test]
+async fn fail_update_with_wrong_history() {
+    let (mut context, test_randomness_oracle, _) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_randomness_oracle(
+            &id(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &context.payer.pubkey(),
+            Some(&Keypair::new().pubkey()),
+            [1u8; 32],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidHistory)
+    );
+}
@@ -1,7 +1,8 @@
 mod utils;
 
+use randomness_oracle_program::{error::RandomnessOracleError, state::RandomnessOracle};
 use solana_program_test::*;
-use solana_sdk::signer::Signer;
+use solana_sdk::{program_pack::Pack, signature::Keypair, signer::Signer};
 use utils::*;
 
 #[tokio::test]
@@ -15,3 +16,58 @@ async fn success() {
     assert_eq!(randomness_oracle.slot, 1);
     assert_eq!(randomness_oracle.value, [0u8; 32]);
 }
+
+#[tokio::test]
+async fn fail_already_initialized() {
+    let mut context = program_test().start_with_context().await;
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    let attacker = Keypair::new();
+    assert_eq!(
+        test_randomness_oracle
+            .reinit(&mut context, &attacker)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::AlreadyInitialized)
+    );
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.authority, context.payer.pubkey());
+}
+
+#[tokio::test]
+async fn fail_wrong_account_size() {
+    let mut context = program_test().start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let space = RandomnessOracle::LEN + 1;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    // Init instruction follows the account creation
+    assert_eq!(
+        test_randomness_oracle
+            .init_with_account(&mut context, rent.minimum_balance(space), space, 0)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::WrongAccountSize)
+    );
+}
+
+#[tokio::test]
+async fn fail_not_rent_exempt() {
+    let mut context = program_test().start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let space = RandomnessOracle::LEN;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    assert_eq!(
+        test_randomness_oracle
+            .init_with_account(&mut context, rent.minimum_balance(space) - 1, space, 0)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::NotRentExempt)
+    );
+}
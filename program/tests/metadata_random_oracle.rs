@@ -0,0 +1,109 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    id, instruction,
+    state::{RandomnessOracleMetadata, MAX_NAME_LEN, MAX_URI_LEN},
+};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use utils::*;
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    (context, test_randomness_oracle)
+}
+
+fn metadata() -> RandomnessOracleMetadata {
+    RandomnessOracleMetadata {
+        name: "a".repeat(MAX_NAME_LEN),
+        uri: "b".repeat(MAX_URI_LEN),
+        update_interval_slots: 10,
+    }
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle) = setup().await;
+
+    test_randomness_oracle
+        .update_metadata(&mut context, metadata())
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.metadata(), metadata());
+
+    // Shorter values replace the previous ones entirely
+    let short_metadata = RandomnessOracleMetadata {
+        name: "c".to_string(),
+        uri: String::new(),
+        update_interval_slots: 0,
+    };
+    test_randomness_oracle
+        .update_metadata(&mut context, short_metadata.clone())
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.metadata(), short_metadata);
+}
+
+#[tokio::test]
+async fn fail_too_long() {
+    let (mut context, test_randomness_oracle) = setup().await;
+
+    let mut long_name = metadata();
+    long_name.name.push('a');
+    assert_eq!(
+        test_randomness_oracle
+            .update_metadata(&mut context, long_name)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidMetadata)
+    );
+
+    let mut long_uri = metadata();
+    long_uri.uri.push('b');
+    assert_eq!(
+        test_randomness_oracle
+            .update_metadata(&mut context, long_uri)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidMetadata)
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_authority() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let fake_authority = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_metadata(
+            &id(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &fake_authority.pubkey(),
+            metadata(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_authority],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+}
@@ -0,0 +1,241 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    find_registry_address, id, instruction,
+    state::{
+        AccountType, RandomnessOracle, RandomnessOracleRegistry, RandomnessOracleV1,
+        RANDOMNESS_ORACLE_VERSION,
+    },
+};
+use solana_program::instruction::InstructionError;
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account, program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    system_instruction, transaction::Transaction, transport,
+};
+use utils::*;
+
+const VALUE: [u8; 32] = [1u8; 32];
+const SLOT: u64 = 1;
+
+async fn setup(authority: &Pubkey) -> (ProgramTestContext, Pubkey) {
+    let mut test = program_test();
+
+    let mut data = vec![0u8; RandomnessOracleV1::LEN];
+    RandomnessOracleV1 {
+        account_type: AccountType::RandomnessOracle,
+        authority: *authority,
+        value: VALUE,
+        slot: SLOT,
+    }
+    .pack_into_slice(&mut data);
+
+    let legacy_randomness_oracle = Pubkey::new_unique();
+    test.add_account(
+        legacy_randomness_oracle,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: id(),
+            ..Account::default()
+        },
+    );
+
+    // Authority pays for its registry
+    test.add_account(
+        *authority,
+        Account {
+            lamports: 1_000_000_000,
+            ..Account::default()
+        },
+    );
+
+    (test.start_with_context().await, legacy_randomness_oracle)
+}
+
+async fn migrate(
+    context: &mut ProgramTestContext,
+    legacy_randomness_oracle: &Pubkey,
+    new_randomness_oracle: &Keypair,
+    authority: &Keypair,
+) -> transport::Result<()> {
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &new_randomness_oracle.pubkey(),
+                rent.minimum_balance(RandomnessOracle::LEN),
+                RandomnessOracle::LEN as u64,
+                &id(),
+            ),
+            instruction::migrate_randomness_oracle(
+                &id(),
+                legacy_randomness_oracle,
+                &new_randomness_oracle.pubkey(),
+                &authority.pubkey(),
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, new_randomness_oracle, authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+#[tokio::test]
+async fn success() {
+    let authority = Keypair::new();
+    let (mut context, legacy_randomness_oracle) = setup(&authority.pubkey()).await;
+
+    // Legacy layout is readable
+    let account = get_account(&mut context, &legacy_randomness_oracle).await;
+    let randomness_oracle = RandomnessOracle::unpack(&account.data).unwrap();
+    assert_eq!(randomness_oracle.version, 1);
+    assert_eq!(randomness_oracle.value, VALUE);
+
+    let new_randomness_oracle = Keypair::new();
+    migrate(
+        &mut context,
+        &legacy_randomness_oracle,
+        &new_randomness_oracle,
+        &authority,
+    )
+    .await
+    .unwrap();
+
+    let account = get_account(&mut context, &new_randomness_oracle.pubkey()).await;
+    let randomness_oracle = RandomnessOracle::unpack(&account.data).unwrap();
+    assert_eq!(randomness_oracle.version, RANDOMNESS_ORACLE_VERSION);
+    assert_eq!(randomness_oracle.authority, authority.pubkey());
+    assert_eq!(randomness_oracle.value, VALUE);
+    assert_eq!(randomness_oracle.slot, SLOT);
+
+    let legacy_account = context
+        .banks_client
+        .get_account(legacy_randomness_oracle)
+        .await
+        .unwrap();
+    assert!(legacy_account.is_none());
+
+    let (registry, _) = find_registry_address(&id(), &authority.pubkey());
+    let registry = get_account(&mut context, &registry).await;
+    assert_eq!(
+        RandomnessOracleRegistry::unpack(&registry.data)
+            .unwrap()
+            .oracles(),
+        &[new_randomness_oracle.pubkey()]
+    );
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let authority_account = get_account(&mut context, &authority.pubkey()).await;
+    assert_eq!(
+        authority_account.lamports,
+        2_000_000_000 - rent.minimum_balance(RandomnessOracleRegistry::LEN)
+    );
+}
+
+#[tokio::test]
+async fn success_upgrade_in_place() {
+    let authority = Keypair::new();
+    let mut test = program_test();
+
+    let mut data = vec![0u8; RandomnessOracle::LEN];
+    RandomnessOracle {
+        account_type: AccountType::RandomnessOracle,
+        version: RANDOMNESS_ORACLE_VERSION - 1,
+        authority: authority.pubkey(),
+        value: VALUE,
+        slot: SLOT,
+        ..RandomnessOracle::default()
+    }
+    .pack_into_slice(&mut data);
+
+    let randomness_oracle = Pubkey::new_unique();
+    test.add_account(
+        randomness_oracle,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: id(),
+            ..Account::default()
+        },
+    );
+
+    let mut context = test.start_with_context().await;
+    context.warp_to_slot(3).unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_randomness_oracle(
+            &id(),
+            &randomness_oracle,
+            &authority.pubkey(),
+            None,
+            [2u8; 32],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &authority],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    // Same address keeps the upgraded account
+    let account = get_account(&mut context, &randomness_oracle).await;
+    assert_eq!(account.data.len(), RandomnessOracle::LEN);
+    assert_eq!(account.data[1], RANDOMNESS_ORACLE_VERSION);
+
+    let randomness_oracle = RandomnessOracle::unpack(&account.data).unwrap();
+    assert_eq!(randomness_oracle.authority, authority.pubkey());
+    assert_eq!(randomness_oracle.value, [2u8; 32]);
+}
+
+#[tokio::test]
+async fn fail_update_legacy() {
+    let authority = Keypair::new();
+    let (mut context, legacy_randomness_oracle) = setup(&authority.pubkey()).await;
+    context.warp_to_slot(3).unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_randomness_oracle(
+            &id(),
+            &legacy_randomness_oracle,
+            &authority.pubkey(),
+            None,
+            [2u8; 32],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &authority],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        instruction_error(0, InstructionError::InvalidAccountData)
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_authority() {
+    let (mut context, legacy_randomness_oracle) = setup(&Keypair::new().pubkey()).await;
+
+    assert_eq!(
+        migrate(
+            &mut context,
+            &legacy_randomness_oracle,
+            &Keypair::new(),
+            &Keypair::new(),
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        custom_error(1, RandomnessOracleError::InvalidAuthority)
+    );
+}
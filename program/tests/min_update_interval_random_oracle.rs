@@ -0,0 +1,81 @@
+mod utils;
+
+use randomness_oracle_program::{error::RandomnessOracleError, id, instruction};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use utils::*;
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+    test_randomness_oracle
+        .set_min_update_interval(&mut context, 5)
+        .await
+        .unwrap();
+
+    (context, test_randomness_oracle)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle) = setup().await;
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.min_update_interval_slots, 5);
+
+    context.warp_to_slot(randomness_oracle.slot + 5).unwrap();
+    test_randomness_oracle
+        .update(&mut context, [1u8; 32])
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.value, [1u8; 32]);
+}
+
+#[tokio::test]
+async fn fail_update_too_early() {
+    let (mut context, test_randomness_oracle) = setup().await;
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    context.warp_to_slot(randomness_oracle.slot + 4).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .update(&mut context, [1u8; 32])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::UpdateTooEarly)
+    );
+}
+
+#[tokio::test]
+async fn fail_set_wrong_authority() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let fake_authority = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::set_min_update_interval(
+            &id(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &fake_authority.pubkey(),
+            0,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_authority],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+}
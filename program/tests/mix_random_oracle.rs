@@ -0,0 +1,147 @@
+mod utils;
+
+use randomness_oracle_program::{error::RandomnessOracleError, state::UpdateMode};
+use solana_program::{
+    hash::{hash, hashv},
+    slot_hashes::SlotHashes,
+};
+use solana_program_test::*;
+use utils::*;
+
+const VALUE: [u8; 32] = [1u8; 32];
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::with_mode(UpdateMode::Mix);
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    (context, test_randomness_oracle)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&VALUE).to_bytes())
+        .await
+        .unwrap();
+    let previous = test_randomness_oracle.get_data(&mut context).await;
+
+    context.warp_to_slot(5).unwrap();
+
+    test_randomness_oracle
+        .mix(&mut context, VALUE)
+        .await
+        .unwrap();
+
+    // Value is mixed with the hash of the commitment slot
+    let slot_hashes = context
+        .banks_client
+        .get_sysvar::<SlotHashes>()
+        .await
+        .unwrap();
+    let slot_hash = slot_hashes.get(&3).unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert!(!randomness_oracle.has_commitment());
+    assert_eq!(randomness_oracle.slot, 5);
+    assert_eq!(randomness_oracle.submission, VALUE);
+    assert_eq!(randomness_oracle.slot_hash, slot_hash.to_bytes());
+    assert_eq!(
+        randomness_oracle.value,
+        hashv(&[&VALUE, &previous.value, slot_hash.as_ref()]).to_bytes()
+    );
+}
+
+#[tokio::test]
+async fn fail_mix_without_commitment() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .mix(&mut context, VALUE)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::NoPendingCommitment)
+    );
+}
+
+#[tokio::test]
+async fn fail_mix_wrong_value() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&VALUE).to_bytes())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .mix(&mut context, [2u8; 32])
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::CommitmentMismatch)
+    );
+}
+
+#[tokio::test]
+async fn fail_mix_in_commit_slot() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&VALUE).to_bytes())
+        .await
+        .unwrap();
+
+    // Commitment slot hash isn't known yet
+    assert_eq!(
+        test_randomness_oracle
+            .mix(&mut context, VALUE)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::RevealTooEarly)
+    );
+}
+
+#[tokio::test]
+async fn fail_update_mix_mode() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .update(&mut context, VALUE)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidUpdateMode)
+    );
+}
+
+#[tokio::test]
+async fn fail_mix_direct_mode() {
+    let mut context = program_test().start_with_context().await;
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .mix(&mut context, VALUE)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidUpdateMode)
+    );
+}
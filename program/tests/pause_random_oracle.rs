@@ -0,0 +1,95 @@
+mod utils;
+
+use randomness_oracle_program::{error::RandomnessOracleError, id, instruction};
+use solana_program::hash::hash;
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use utils::*;
+
+const VALUE: [u8; 32] = [1u8; 32];
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    (context, test_randomness_oracle)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle) = setup().await;
+
+    test_randomness_oracle.pause(&mut context).await.unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert!(randomness_oracle.paused);
+
+    test_randomness_oracle.resume(&mut context).await.unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert!(!randomness_oracle.paused);
+
+    test_randomness_oracle
+        .update(&mut context, VALUE)
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.value, VALUE);
+}
+
+#[tokio::test]
+async fn fail_update_paused() {
+    let (mut context, test_randomness_oracle) = setup().await;
+
+    test_randomness_oracle.pause(&mut context).await.unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .update(&mut context, VALUE)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::OraclePaused)
+    );
+    assert_eq!(
+        test_randomness_oracle
+            .commit(&mut context, hash(&VALUE).to_bytes())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::OraclePaused)
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_authority() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    let fake_authority = Keypair::new();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::pause_randomness_oracle(
+            &id(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &fake_authority.pubkey(),
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_authority],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert!(!randomness_oracle.paused);
+}
@@ -0,0 +1,353 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    find_program_config_address, id, instruction,
+    state::{AccountType, ProgramConfig, RandomnessOracleMetadata},
+};
+use solana_program::{
+    bpf_loader_upgradeable, instruction::InstructionError, system_instruction::SystemError,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account, program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer,
+    transaction::Transaction, transport,
+};
+use utils::*;
+
+/// Program data account of the program, as written by the upgradeable loader.
+fn program_data_account(upgrade_authority: &Pubkey) -> Account {
+    let mut data = vec![0u8; 45];
+    data[0..4].copy_from_slice(&3u32.to_le_bytes());
+    data[12] = 1;
+    data[13..45].copy_from_slice(upgrade_authority.as_ref());
+
+    Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: bpf_loader_upgradeable::id(),
+        ..Account::default()
+    }
+}
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle, Keypair, Keypair) {
+    let mut test = program_test();
+
+    let upgrade_authority = Keypair::new();
+    test.add_account(
+        upgrade_authority.pubkey(),
+        Account {
+            lamports: 1_000_000_000,
+            ..Account::default()
+        },
+    );
+    let (program_data, _) =
+        Pubkey::find_program_address(&[id().as_ref()], &bpf_loader_upgradeable::id());
+    test.add_account(
+        program_data,
+        program_data_account(&upgrade_authority.pubkey()),
+    );
+
+    let mut context = test.start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    let admin = Keypair::new();
+
+    (context, test_randomness_oracle, upgrade_authority, admin)
+}
+
+async fn init_program_config(
+    context: &mut ProgramTestContext,
+    upgrade_authority: &Keypair,
+    admin: &Pubkey,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::init_program_config(
+            &id(),
+            &upgrade_authority.pubkey(),
+            admin,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, upgrade_authority],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn approve(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    randomness_oracle: &Pubkey,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::approve_oracle(
+            &id(),
+            &admin.pubkey(),
+            randomness_oracle,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, admin],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn revoke(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    randomness_oracle: &Pubkey,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::revoke_oracle(
+            &id(),
+            &admin.pubkey(),
+            randomness_oracle,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, admin],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn propose_admin(
+    context: &mut ProgramTestContext,
+    admin: &Keypair,
+    new_admin: &Pubkey,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::propose_admin(
+            &id(),
+            &admin.pubkey(),
+            new_admin,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, admin],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn accept_admin(
+    context: &mut ProgramTestContext,
+    new_admin: &Keypair,
+) -> transport::Result<()> {
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::accept_admin(&id(), &new_admin.pubkey())],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, new_admin],
+        context.last_blockhash,
+    );
+
+    context.banks_client.process_transaction(tx).await
+}
+
+async fn get_program_config(context: &mut ProgramTestContext) -> ProgramConfig {
+    let (program_config, _) = find_program_config_address(&id());
+    let program_config_account = get_account(context, &program_config).await;
+    ProgramConfig::unpack(&program_config_account.data).unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle, upgrade_authority, admin) = setup().await;
+    let randomness_oracle = test_randomness_oracle.keypair.pubkey();
+
+    init_program_config(&mut context, &upgrade_authority, &admin.pubkey())
+        .await
+        .unwrap();
+
+    let program_config = get_program_config(&mut context).await;
+    assert_eq!(program_config.account_type, AccountType::ProgramConfig);
+    assert_eq!(program_config.admin, admin.pubkey());
+
+    approve(&mut context, &admin, &randomness_oracle)
+        .await
+        .unwrap();
+    assert!(test_randomness_oracle.get_data(&mut context).await.approved);
+
+    revoke(&mut context, &admin, &randomness_oracle)
+        .await
+        .unwrap();
+    assert!(!test_randomness_oracle.get_data(&mut context).await.approved);
+}
+
+#[tokio::test]
+async fn fail_init_not_upgrade_authority() {
+    let (mut context, _, _, admin) = setup().await;
+    let fake_upgrade_authority = Keypair::new();
+
+    assert_eq!(
+        init_program_config(&mut context, &fake_upgrade_authority, &admin.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+}
+
+#[tokio::test]
+async fn fail_init_twice() {
+    let (mut context, _, upgrade_authority, admin) = setup().await;
+
+    init_program_config(&mut context, &upgrade_authority, &admin.pubkey())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+
+    assert_eq!(
+        init_program_config(&mut context, &upgrade_authority, &Pubkey::new_unique())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        instruction_error(
+            0,
+            InstructionError::Custom(SystemError::AccountAlreadyInUse as u32)
+        )
+    );
+}
+
+#[tokio::test]
+async fn fail_approve_wrong_admin() {
+    let (mut context, test_randomness_oracle, upgrade_authority, admin) = setup().await;
+
+    init_program_config(&mut context, &upgrade_authority, &admin.pubkey())
+        .await
+        .unwrap();
+
+    let fake_admin = Keypair::new();
+    assert_eq!(
+        approve(
+            &mut context,
+            &fake_admin,
+            &test_randomness_oracle.keypair.pubkey()
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+    assert!(!test_randomness_oracle.get_data(&mut context).await.approved);
+}
+
+#[tokio::test]
+async fn success_transfer_admin() {
+    let (mut context, test_randomness_oracle, upgrade_authority, admin) = setup().await;
+    let randomness_oracle = test_randomness_oracle.keypair.pubkey();
+    let new_admin = Keypair::new();
+
+    init_program_config(&mut context, &upgrade_authority, &admin.pubkey())
+        .await
+        .unwrap();
+
+    propose_admin(&mut context, &admin, &new_admin.pubkey())
+        .await
+        .unwrap();
+    let program_config = get_program_config(&mut context).await;
+    assert_eq!(program_config.admin, admin.pubkey());
+    assert_eq!(program_config.pending_admin, new_admin.pubkey());
+
+    accept_admin(&mut context, &new_admin).await.unwrap();
+    let program_config = get_program_config(&mut context).await;
+    assert_eq!(program_config.admin, new_admin.pubkey());
+    assert_eq!(program_config.pending_admin, Pubkey::default());
+
+    // Previous admin can't approve anymore
+    assert_eq!(
+        approve(&mut context, &admin, &randomness_oracle)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+    approve(&mut context, &new_admin, &randomness_oracle)
+        .await
+        .unwrap();
+    assert!(test_randomness_oracle.get_data(&mut context).await.approved);
+}
+
+#[tokio::test]
+async fn fail_propose_wrong_admin() {
+    let (mut context, _, upgrade_authority, admin) = setup().await;
+    let fake_admin = Keypair::new();
+
+    init_program_config(&mut context, &upgrade_authority, &admin.pubkey())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        propose_admin(&mut context, &fake_admin, &fake_admin.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+}
+
+#[tokio::test]
+async fn fail_accept_not_proposed_admin() {
+    let (mut context, _, upgrade_authority, admin) = setup().await;
+    let new_admin = Keypair::new();
+    let fake_admin = Keypair::new();
+
+    init_program_config(&mut context, &upgrade_authority, &admin.pubkey())
+        .await
+        .unwrap();
+
+    // Nothing proposed yet
+    assert_eq!(
+        accept_admin(&mut context, &new_admin)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidPendingAuthority)
+    );
+
+    propose_admin(&mut context, &admin, &new_admin.pubkey())
+        .await
+        .unwrap();
+    assert_eq!(
+        accept_admin(&mut context, &fake_admin)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidPendingAuthority)
+    );
+
+    let program_config = get_program_config(&mut context).await;
+    assert_eq!(program_config.admin, admin.pubkey());
+}
+
+#[tokio::test]
+async fn success_update_metadata_drops_approval() {
+    let (mut context, test_randomness_oracle, upgrade_authority, admin) = setup().await;
+    let randomness_oracle = test_randomness_oracle.keypair.pubkey();
+
+    init_program_config(&mut context, &upgrade_authority, &admin.pubkey())
+        .await
+        .unwrap();
+    approve(&mut context, &admin, &randomness_oracle)
+        .await
+        .unwrap();
+
+    test_randomness_oracle
+        .update_metadata(
+            &mut context,
+            RandomnessOracleMetadata {
+                name: "coin flip".to_string(),
+                uri: "https://example.com/coin-flip.json".to_string(),
+                update_interval_slots: 10,
+            },
+        )
+        .await
+        .unwrap();
+
+    assert!(!test_randomness_oracle.get_data(&mut context).await.approved);
+}
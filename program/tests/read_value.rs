@@ -0,0 +1,368 @@
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    id, read_value, read_value_approved, read_value_at_slot, read_value_checked, read_value_fresh,
+    read_value_fresh_approved, read_value_fresh_by_time, read_value_settled,
+    state::{
+        AccountType, RandomnessOracle, RandomnessOracleHistory, RandomnessOracleV1, HISTORY_LEN,
+        RANDOMNESS_ORACLE_RESERVED_LEN,
+    },
+};
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, program_error::ProgramError, program_pack::Pack,
+    pubkey::Pubkey,
+};
+
+const VALUE: [u8; 32] = [1u8; 32];
+const SLOT: u64 = 10;
+const UNIX_TIMESTAMP: i64 = 100;
+const ROUND: u64 = 5;
+
+struct TestAccount {
+    key: Pubkey,
+    owner: Pubkey,
+    lamports: u64,
+    data: Vec<u8>,
+}
+
+impl TestAccount {
+    fn new(owner: Pubkey, randomness_oracle: RandomnessOracle) -> Self {
+        let mut data = vec![0u8; RandomnessOracle::LEN];
+        RandomnessOracle::pack(randomness_oracle, &mut data).unwrap();
+
+        Self::with_data(owner, data)
+    }
+
+    fn new_history(owner: Pubkey, history: RandomnessOracleHistory) -> Self {
+        let mut data = vec![0u8; RandomnessOracleHistory::LEN];
+        RandomnessOracleHistory::pack(history, &mut data).unwrap();
+
+        Self::with_data(owner, data)
+    }
+
+    fn with_data(owner: Pubkey, data: Vec<u8>) -> Self {
+        Self {
+            key: Pubkey::new_unique(),
+            owner,
+            lamports: 0,
+            data,
+        }
+    }
+
+    fn info(&mut self) -> AccountInfo {
+        AccountInfo::new(
+            &self.key,
+            false,
+            false,
+            &mut self.lamports,
+            &mut self.data,
+            &self.owner,
+            false,
+            0,
+        )
+    }
+}
+
+fn randomness_oracle(authority: Pubkey) -> RandomnessOracle {
+    RandomnessOracle {
+        account_type: AccountType::RandomnessOracle,
+        authority,
+        value: VALUE,
+        slot: SLOT,
+        unix_timestamp: UNIX_TIMESTAMP,
+        round: ROUND,
+        ..Default::default()
+    }
+}
+
+/// History of randomness oracle with values `[i; 32]` published at slots `SLOT + i` in rounds `i`.
+fn history(randomness_oracle: Pubkey, count: u8) -> RandomnessOracleHistory {
+    let mut history = RandomnessOracleHistory {
+        account_type: AccountType::RandomnessOracleHistory,
+        randomness_oracle,
+        ..Default::default()
+    };
+    for i in 0..count {
+        history.push(SLOT + i as u64, i as u64, [i; 32]);
+    }
+
+    history
+}
+
+fn clock(slot: u64, unix_timestamp: i64) -> Clock {
+    Clock {
+        slot,
+        unix_timestamp,
+        ..Clock::default()
+    }
+}
+
+#[test]
+fn read_value_checked_success() {
+    let authority = Pubkey::new_unique();
+    let mut account = TestAccount::new(id(), randomness_oracle(authority));
+
+    assert_eq!(
+        read_value_checked(&account.info(), None),
+        Ok((VALUE, SLOT, ROUND))
+    );
+    assert_eq!(
+        read_value_checked(&account.info(), Some(&authority)),
+        Ok((VALUE, SLOT, ROUND))
+    );
+}
+
+#[test]
+fn read_value_checked_success_legacy() {
+    let authority = Pubkey::new_unique();
+    let mut data = vec![0u8; RandomnessOracleV1::LEN];
+    RandomnessOracleV1 {
+        account_type: AccountType::RandomnessOracle,
+        authority,
+        value: VALUE,
+        slot: SLOT,
+    }
+    .pack_into_slice(&mut data);
+    let mut account = TestAccount::with_data(id(), data);
+
+    assert_eq!(
+        read_value_checked(&account.info(), Some(&authority)),
+        Ok((VALUE, SLOT, 0))
+    );
+}
+
+#[test]
+fn read_value_checked_success_reserved_bytes() {
+    let authority = Pubkey::new_unique();
+    let mut account = TestAccount::new(id(), randomness_oracle(authority));
+
+    // Fields added later into the reserved area don't break older readers
+    let len = account.data.len();
+    account.data[len - RANDOMNESS_ORACLE_RESERVED_LEN..]
+        .copy_from_slice(&[7u8; RANDOMNESS_ORACLE_RESERVED_LEN]);
+
+    assert_eq!(
+        read_value_checked(&account.info(), Some(&authority)),
+        Ok((VALUE, SLOT, ROUND))
+    );
+}
+
+#[test]
+fn read_value_checked_fail_wrong_owner() {
+    let mut account = TestAccount::new(
+        Pubkey::new_unique(),
+        randomness_oracle(Pubkey::new_unique()),
+    );
+
+    assert_eq!(
+        read_value_checked(&account.info(), None),
+        Err(ProgramError::IllegalOwner)
+    );
+}
+
+#[test]
+fn read_value_checked_fail_uninitialized() {
+    let mut account = TestAccount::new(id(), RandomnessOracle::default());
+
+    assert_eq!(
+        read_value_checked(&account.info(), None),
+        Err(RandomnessOracleError::InvalidAccountType.into())
+    );
+}
+
+#[test]
+fn read_value_checked_fail_wrong_authority() {
+    let mut account = TestAccount::new(id(), randomness_oracle(Pubkey::new_unique()));
+
+    assert_eq!(
+        read_value_checked(&account.info(), Some(&Pubkey::new_unique())),
+        Err(RandomnessOracleError::InvalidAuthority.into())
+    );
+}
+
+#[test]
+fn read_value_checked_fail_paused() {
+    let mut account = TestAccount::new(
+        id(),
+        RandomnessOracle {
+            paused: true,
+            ..randomness_oracle(Pubkey::new_unique())
+        },
+    );
+
+    assert_eq!(
+        read_value(&account.info()),
+        Err(RandomnessOracleError::OraclePaused.into())
+    );
+    assert_eq!(
+        read_value_checked(&account.info(), None),
+        Err(RandomnessOracleError::OraclePaused.into())
+    );
+    assert_eq!(
+        read_value_settled(&account.info(), &clock(SLOT + 1, UNIX_TIMESTAMP)),
+        Err(RandomnessOracleError::OraclePaused.into())
+    );
+}
+
+#[test]
+fn read_value_fresh_success() {
+    let mut account = TestAccount::new(id(), randomness_oracle(Pubkey::new_unique()));
+
+    assert_eq!(
+        read_value_fresh(&account.info(), &clock(SLOT + 5, UNIX_TIMESTAMP), 5),
+        Ok((VALUE, SLOT, ROUND))
+    );
+    assert_eq!(
+        read_value_fresh_by_time(&account.info(), &clock(SLOT, UNIX_TIMESTAMP + 60), 60),
+        Ok((VALUE, SLOT, ROUND))
+    );
+}
+
+#[test]
+fn read_value_fresh_fail_stale() {
+    let mut account = TestAccount::new(id(), randomness_oracle(Pubkey::new_unique()));
+
+    assert_eq!(
+        read_value_fresh(&account.info(), &clock(SLOT + 6, UNIX_TIMESTAMP), 5),
+        Err(RandomnessOracleError::StaleValue.into())
+    );
+    assert_eq!(
+        read_value_fresh_by_time(&account.info(), &clock(SLOT, UNIX_TIMESTAMP + 61), 60),
+        Err(RandomnessOracleError::StaleValue.into())
+    );
+}
+
+#[test]
+fn read_value_approved_success() {
+    let mut account = TestAccount::new(
+        id(),
+        RandomnessOracle {
+            approved: true,
+            ..randomness_oracle(Pubkey::new_unique())
+        },
+    );
+
+    assert_eq!(
+        read_value_approved(&account.info()),
+        Ok((VALUE, SLOT, ROUND))
+    );
+    assert_eq!(
+        read_value_fresh_approved(&account.info(), &clock(SLOT + 5, UNIX_TIMESTAMP), 5),
+        Ok((VALUE, SLOT, ROUND))
+    );
+}
+
+#[test]
+fn read_value_approved_fail_not_approved() {
+    let mut account = TestAccount::new(id(), randomness_oracle(Pubkey::new_unique()));
+
+    assert_eq!(
+        read_value_approved(&account.info()),
+        Err(RandomnessOracleError::OracleNotApproved.into())
+    );
+    assert_eq!(
+        read_value_fresh_approved(&account.info(), &clock(SLOT + 5, UNIX_TIMESTAMP), 5),
+        Err(RandomnessOracleError::OracleNotApproved.into())
+    );
+}
+
+#[test]
+fn read_value_settled_success() {
+    let mut account = TestAccount::new(id(), randomness_oracle(Pubkey::new_unique()));
+
+    assert_eq!(
+        read_value_settled(&account.info(), &clock(SLOT + 1, UNIX_TIMESTAMP)),
+        Ok((VALUE, SLOT, ROUND))
+    );
+}
+
+#[test]
+fn read_value_settled_fail_current_slot() {
+    let mut account = TestAccount::new(id(), randomness_oracle(Pubkey::new_unique()));
+
+    assert_eq!(
+        read_value_settled(&account.info(), &clock(SLOT, UNIX_TIMESTAMP)),
+        Err(RandomnessOracleError::ValueNotSettled.into())
+    );
+}
+
+#[test]
+fn read_value_at_slot_success() {
+    let randomness_oracle = Pubkey::new_unique();
+    let mut account = TestAccount::new_history(id(), history(randomness_oracle, 3));
+
+    assert_eq!(
+        read_value_at_slot(&account.info(), &randomness_oracle, SLOT + 1),
+        Ok(([1u8; 32], SLOT + 1, 1))
+    );
+    assert_eq!(
+        read_value_at_slot(&account.info(), &randomness_oracle, SLOT + 2),
+        Ok(([2u8; 32], SLOT + 2, 2))
+    );
+}
+
+#[test]
+fn read_value_at_slot_success_skipped_slot() {
+    let randomness_oracle = Pubkey::new_unique();
+    let mut history = history(randomness_oracle, 1);
+    history.push(SLOT + 5, 1, [5u8; 32]);
+    let mut account = TestAccount::new_history(id(), history);
+
+    assert_eq!(
+        read_value_at_slot(&account.info(), &randomness_oracle, SLOT + 3),
+        Ok(([5u8; 32], SLOT + 5, 1))
+    );
+}
+
+#[test]
+fn read_value_at_slot_success_wrapped() {
+    let randomness_oracle = Pubkey::new_unique();
+    let count = HISTORY_LEN as u8 + 10;
+    let mut account = TestAccount::new_history(id(), history(randomness_oracle, count));
+
+    assert_eq!(
+        read_value_at_slot(&account.info(), &randomness_oracle, SLOT + 11),
+        Ok(([11u8; 32], SLOT + 11, 11))
+    );
+    assert_eq!(
+        read_value_at_slot(&account.info(), &randomness_oracle, SLOT + count as u64 - 1),
+        Ok(([count - 1; 32], SLOT + count as u64 - 1, count as u64 - 1))
+    );
+}
+
+#[test]
+fn read_value_at_slot_fail_expired() {
+    let randomness_oracle = Pubkey::new_unique();
+    let mut account =
+        TestAccount::new_history(id(), history(randomness_oracle, HISTORY_LEN as u8 + 10));
+
+    // Oldest kept entry can't prove there was no earlier value at or after the slot
+    assert_eq!(
+        read_value_at_slot(&account.info(), &randomness_oracle, SLOT + 10),
+        Err(RandomnessOracleError::HistoryExpired.into())
+    );
+    assert_eq!(
+        read_value_at_slot(&account.info(), &randomness_oracle, SLOT),
+        Err(RandomnessOracleError::HistoryExpired.into())
+    );
+}
+
+#[test]
+fn read_value_at_slot_fail_not_published() {
+    let randomness_oracle = Pubkey::new_unique();
+    let mut account = TestAccount::new_history(id(), history(randomness_oracle, 3));
+
+    assert_eq!(
+        read_value_at_slot(&account.info(), &randomness_oracle, SLOT + 3),
+        Err(RandomnessOracleError::ValueNotPublished.into())
+    );
+}
+
+#[test]
+fn read_value_at_slot_fail_wrong_oracle() {
+    let mut account = TestAccount::new_history(id(), history(Pubkey::new_unique(), 3));
+
+    assert_eq!(
+        read_value_at_slot(&account.info(), &Pubkey::new_unique(), SLOT + 1),
+        Err(RandomnessOracleError::InvalidHistory.into())
+    );
+}
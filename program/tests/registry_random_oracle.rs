@@ -0,0 +1,98 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    find_registry_address, id, instruction,
+    state::{
+        RandomnessOracle, RandomnessOracleMetadata, RandomnessOracleRegistry, UpdateMode,
+        MIN_BOND_LAMPORTS,
+    },
+};
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use utils::*;
+
+async fn get_registry(
+    context: &mut ProgramTestContext,
+    authority: &Pubkey,
+) -> RandomnessOracleRegistry {
+    let (registry, _) = find_registry_address(&id(), authority);
+    let account = get_account(context, &registry).await;
+    assert_eq!(account.owner, id());
+
+    RandomnessOracleRegistry::unpack(&account.data).unwrap()
+}
+
+#[tokio::test]
+async fn success() {
+    let mut context = program_test().start_with_context().await;
+    let authority = context.payer.pubkey();
+
+    let first = TestRandomnessOracle::new();
+    first.init(&mut context).await.unwrap();
+    let second = TestRandomnessOracle::new();
+    second.init(&mut context).await.unwrap();
+
+    let registry = get_registry(&mut context, &authority).await;
+    assert_eq!(registry.authority, authority);
+    assert_eq!(
+        registry.oracles(),
+        &[first.keypair.pubkey(), second.keypair.pubkey()]
+    );
+
+    first
+        .close(&mut context, &Keypair::new().pubkey())
+        .await
+        .unwrap();
+
+    let registry = get_registry(&mut context, &authority).await;
+    assert_eq!(registry.oracles(), &[second.keypair.pubkey()]);
+}
+
+#[tokio::test]
+async fn fail_wrong_registry() {
+    let mut context = program_test().start_with_context().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let randomness_oracle = Keypair::new();
+
+    let mut init = instruction::init_randomness_oracle(
+        &id(),
+        &randomness_oracle.pubkey(),
+        &context.payer.pubkey(),
+        0,
+        RandomnessOracleMetadata::default(),
+        MIN_BOND_LAMPORTS,
+        UpdateMode::Direct,
+    );
+    let (fake_registry, _) = find_registry_address(&id(), &Pubkey::new_unique());
+    init.accounts[6].pubkey = fake_registry;
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &randomness_oracle.pubkey(),
+                rent.minimum_balance(RandomnessOracle::LEN),
+                RandomnessOracle::LEN as u64,
+                &id(),
+            ),
+            init,
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &randomness_oracle],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::InvalidRegistry)
+    );
+}
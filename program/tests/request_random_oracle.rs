@@ -0,0 +1,713 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    id, instruction, read_request_value,
+    state::{
+        CallbackAccount, RandomnessRequest, MAX_CALLBACK_ACCOUNTS, MAX_QUEUE_REQUESTS,
+        REQUEST_EXPIRY_SLOTS,
+    },
+};
+use solana_program::{
+    account_info::{next_account_info, AccountInfo},
+    entrypoint::ProgramResult,
+    hash::hashv,
+    pubkey::Pubkey,
+    slot_hashes::SlotHashes,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    account::Account, program_pack::Pack, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use utils::*;
+
+const SEED: [u8; 32] = [1u8; 32];
+
+// Consumer account: requester | value | callback data
+const CONSUMER_LEN: usize = 65;
+
+fn process_callback(_program_id: &Pubkey, accounts: &[AccountInfo], input: &[u8]) -> ProgramResult {
+    let account_info_iter = &mut accounts.iter();
+    let randomness_request_info = next_account_info(account_info_iter)?;
+    let consumer_info = next_account_info(account_info_iter)?;
+
+    let mut data = consumer_info.data.borrow_mut();
+    let requester = Pubkey::new(&data[..32]);
+    let (value, _) = read_request_value(randomness_request_info, &requester)?;
+
+    data[32..64].copy_from_slice(&value);
+    data[64] = input[0];
+
+    Ok(())
+}
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRandomnessOracle,
+    Keypair,
+    Pubkey,
+    Pubkey,
+) {
+    let mut test = program_test();
+
+    let callback_program = Pubkey::new_unique();
+    test.add_program(
+        "randomness_callback",
+        callback_program,
+        processor!(process_callback),
+    );
+
+    // Requester pays for the request queue on the first request
+    let requester = Keypair::new();
+    test.add_account(
+        requester.pubkey(),
+        Account {
+            lamports: 1_000_000_000,
+            ..Account::default()
+        },
+    );
+
+    let consumer = Pubkey::new_unique();
+    let mut data = vec![0u8; CONSUMER_LEN];
+    data[..32].copy_from_slice(requester.pubkey().as_ref());
+    test.add_account(
+        consumer,
+        Account {
+            lamports: 1_000_000_000,
+            data,
+            owner: callback_program,
+            ..Account::default()
+        },
+    );
+
+    let mut context = test.start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    (
+        context,
+        test_randomness_oracle,
+        requester,
+        callback_program,
+        consumer,
+    )
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &randomness_request,
+            &requester,
+            None,
+            SEED,
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    let request = test_randomness_oracle
+        .get_request(&mut context, &randomness_request.pubkey())
+        .await;
+    assert!(!request.fulfilled);
+    assert_eq!(request.requester, requester.pubkey());
+    assert_eq!(request.seed, SEED);
+
+    let request_queue = test_randomness_oracle.get_request_queue(&mut context).await;
+    assert_eq!(request_queue.requests(), &[randomness_request.pubkey()]);
+
+    context.warp_to_slot(3).unwrap();
+    test_randomness_oracle
+        .fulfill(&mut context, &randomness_request.pubkey())
+        .await
+        .unwrap();
+
+    // Fulfilled request leaves the queue
+    let request_queue = test_randomness_oracle.get_request_queue(&mut context).await;
+    assert!(request_queue.requests().is_empty());
+
+    let request = test_randomness_oracle
+        .get_request(&mut context, &randomness_request.pubkey())
+        .await;
+    assert!(request.fulfilled);
+
+    let slot_hashes = context
+        .banks_client
+        .get_sysvar::<SlotHashes>()
+        .await
+        .unwrap();
+    let slot_hash = slot_hashes.get(&request.slot).unwrap();
+    assert_eq!(
+        request.value,
+        hashv(&[
+            slot_hash.as_ref(),
+            &SEED,
+            randomness_request.pubkey().as_ref()
+        ])
+        .to_bytes()
+    );
+}
+
+#[tokio::test]
+async fn fail_fulfill_in_request_slot() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+
+    // Request slot hash isn't known until the next slot
+    let randomness_request = request(&mut context, &test_randomness_oracle, &requester).await;
+    assert_eq!(
+        test_randomness_oracle
+            .fulfill(&mut context, &randomness_request.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::SlotHashNotFound)
+    );
+}
+
+#[tokio::test]
+async fn success_with_callback() {
+    let (mut context, test_randomness_oracle, requester, callback_program, consumer) =
+        setup().await;
+
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &randomness_request,
+            &requester,
+            None,
+            SEED,
+            Some(callback_program),
+            vec![CallbackAccount {
+                pubkey: consumer,
+                is_writable: true,
+            }],
+            vec![7],
+        )
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+    test_randomness_oracle
+        .fulfill(&mut context, &randomness_request.pubkey())
+        .await
+        .unwrap();
+
+    let request = test_randomness_oracle
+        .get_request(&mut context, &randomness_request.pubkey())
+        .await;
+    let consumer_account = get_account(&mut context, &consumer).await;
+    assert_eq!(consumer_account.data[32..64], request.value);
+    assert_eq!(consumer_account.data[64], 7);
+}
+
+#[tokio::test]
+async fn success_with_callback_and_subscription() {
+    let (mut context, test_randomness_oracle, requester, callback_program, consumer) =
+        setup().await;
+
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let request_fee = rent.minimum_balance(0);
+    test_randomness_oracle
+        .set_request_fee(&mut context, request_fee)
+        .await
+        .unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &requester.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    let subscription = test_randomness_oracle
+        .create_subscription(&mut context, &requester)
+        .await
+        .unwrap();
+    test_randomness_oracle
+        .fund_subscription(&mut context, &subscription, request_fee)
+        .await
+        .unwrap();
+
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &randomness_request,
+            &requester,
+            Some(&subscription),
+            SEED,
+            Some(callback_program),
+            vec![CallbackAccount {
+                pubkey: consumer,
+                is_writable: true,
+            }],
+            vec![7],
+        )
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+    test_randomness_oracle
+        .fulfill(&mut context, &randomness_request.pubkey())
+        .await
+        .unwrap();
+
+    let consumer_account = get_account(&mut context, &consumer).await;
+    assert_eq!(consumer_account.data[64], 7);
+    let subscription_data = test_randomness_oracle
+        .get_subscription(&mut context, &subscription)
+        .await;
+    assert_eq!(subscription_data.balance, 0);
+}
+
+#[tokio::test]
+async fn fail_already_fulfilled() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &randomness_request,
+            &requester,
+            None,
+            SEED,
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+    context.warp_to_slot(3).unwrap();
+    test_randomness_oracle
+        .fulfill(&mut context, &randomness_request.pubkey())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .fulfill(&mut context, &randomness_request.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::RequestAlreadyFulfilled)
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_authority() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &randomness_request,
+            &requester,
+            None,
+            SEED,
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    let fake_authority = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::fulfill_randomness(
+            &id(),
+            &randomness_request.pubkey(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &fake_authority.pubkey(),
+            None,
+            None,
+            &[],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &fake_authority],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_callback_account() {
+    let (mut context, test_randomness_oracle, requester, callback_program, consumer) =
+        setup().await;
+
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &randomness_request,
+            &requester,
+            None,
+            SEED,
+            Some(callback_program),
+            vec![CallbackAccount {
+                pubkey: consumer,
+                is_writable: true,
+            }],
+            vec![7],
+        )
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3).unwrap();
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::fulfill_randomness(
+            &id(),
+            &randomness_request.pubkey(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &context.payer.pubkey(),
+            None,
+            Some(&callback_program),
+            &[CallbackAccount {
+                pubkey: Pubkey::new_unique(),
+                is_writable: true,
+            }],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidCallback)
+    );
+}
+
+#[tokio::test]
+async fn fail_too_many_callback_accounts() {
+    let (mut context, test_randomness_oracle, requester, callback_program, consumer) =
+        setup().await;
+
+    let randomness_request = Keypair::new();
+    assert_eq!(
+        test_randomness_oracle
+            .request(
+                &mut context,
+                &randomness_request,
+                &requester,
+                None,
+                SEED,
+                Some(callback_program),
+                vec![
+                    CallbackAccount {
+                        pubkey: consumer,
+                        is_writable: true,
+                    };
+                    MAX_CALLBACK_ACCOUNTS + 1
+                ],
+                vec![7],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::InvalidCallback)
+    );
+}
+
+async fn request(
+    context: &mut ProgramTestContext,
+    test_randomness_oracle: &TestRandomnessOracle,
+    requester: &Keypair,
+) -> Keypair {
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            context,
+            &randomness_request,
+            requester,
+            None,
+            SEED,
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    randomness_request
+}
+
+#[tokio::test]
+async fn success_cancel_expired() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+    let destination = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let randomness_request = request(&mut context, &test_randomness_oracle, &requester).await;
+    let request = test_randomness_oracle
+        .get_request(&mut context, &randomness_request.pubkey())
+        .await;
+
+    context
+        .warp_to_slot(request.slot + REQUEST_EXPIRY_SLOTS)
+        .unwrap();
+    assert_eq!(
+        test_randomness_oracle
+            .cancel_request(
+                &mut context,
+                &randomness_request.pubkey(),
+                &requester,
+                &destination.pubkey(),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::RequestNotExpired)
+    );
+
+    context
+        .warp_to_slot(request.slot + REQUEST_EXPIRY_SLOTS + 1)
+        .unwrap();
+    test_randomness_oracle
+        .cancel_request(
+            &mut context,
+            &randomness_request.pubkey(),
+            &requester,
+            &destination.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let randomness_request_account = context
+        .banks_client
+        .get_account(randomness_request.pubkey())
+        .await
+        .unwrap();
+    assert!(randomness_request_account.is_none());
+
+    let request_queue = test_randomness_oracle.get_request_queue(&mut context).await;
+    assert!(request_queue.requests().is_empty());
+
+    let destination_account = get_account(&mut context, &destination.pubkey()).await;
+    assert_eq!(
+        destination_account.lamports,
+        rent.minimum_balance(RandomnessRequest::LEN)
+    );
+}
+
+#[tokio::test]
+async fn success_evict_expired() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+    let caller = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let randomness_request = request(&mut context, &test_randomness_oracle, &requester).await;
+    let request = test_randomness_oracle
+        .get_request(&mut context, &randomness_request.pubkey())
+        .await;
+    let requester_lamports = get_account(&mut context, &requester.pubkey())
+        .await
+        .lamports;
+
+    context
+        .warp_to_slot(request.slot + REQUEST_EXPIRY_SLOTS + 1)
+        .unwrap();
+
+    // Lamports of the evicted request go back to the requester
+    assert_eq!(
+        test_randomness_oracle
+            .cancel_request(
+                &mut context,
+                &randomness_request.pubkey(),
+                &caller,
+                &caller.pubkey(),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+
+    test_randomness_oracle
+        .cancel_request(
+            &mut context,
+            &randomness_request.pubkey(),
+            &caller,
+            &requester.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    let request_queue = test_randomness_oracle.get_request_queue(&mut context).await;
+    assert!(request_queue.requests().is_empty());
+
+    let requester_account = get_account(&mut context, &requester.pubkey()).await;
+    assert_eq!(
+        requester_account.lamports,
+        requester_lamports + rent.minimum_balance(RandomnessRequest::LEN)
+    );
+}
+
+#[tokio::test]
+async fn success_evict_full_queue() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+    let caller = Keypair::new();
+
+    for _ in 0..MAX_QUEUE_REQUESTS {
+        request(&mut context, &test_randomness_oracle, &requester).await;
+    }
+
+    let request_queue = test_randomness_oracle.get_request_queue(&mut context).await;
+    let first_request = test_randomness_oracle
+        .get_request(&mut context, &request_queue.requests()[0])
+        .await;
+    context
+        .warp_to_slot(first_request.slot + REQUEST_EXPIRY_SLOTS + 1)
+        .unwrap();
+
+    // Anyone can drain the queue once requests are expired
+    for randomness_request in request_queue.requests() {
+        test_randomness_oracle
+            .cancel_request(
+                &mut context,
+                randomness_request,
+                &caller,
+                &requester.pubkey(),
+            )
+            .await
+            .unwrap();
+    }
+
+    let request_queue = test_randomness_oracle.get_request_queue(&mut context).await;
+    assert!(request_queue.requests().is_empty());
+
+    request(&mut context, &test_randomness_oracle, &requester).await;
+}
+
+#[tokio::test]
+async fn fail_cancel_fulfilled() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+
+    let randomness_request = request(&mut context, &test_randomness_oracle, &requester).await;
+    context.warp_to_slot(3).unwrap();
+    test_randomness_oracle
+        .fulfill(&mut context, &randomness_request.pubkey())
+        .await
+        .unwrap();
+
+    let request = test_randomness_oracle
+        .get_request(&mut context, &randomness_request.pubkey())
+        .await;
+    context
+        .warp_to_slot(request.slot + REQUEST_EXPIRY_SLOTS + 1)
+        .unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .cancel_request(
+                &mut context,
+                &randomness_request.pubkey(),
+                &requester,
+                &requester.pubkey(),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::RequestAlreadyFulfilled)
+    );
+}
+
+#[tokio::test]
+async fn fail_cancel_wrong_requester() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+    let fake_requester = Keypair::new();
+
+    let randomness_request = request(&mut context, &test_randomness_oracle, &requester).await;
+    let request = test_randomness_oracle
+        .get_request(&mut context, &randomness_request.pubkey())
+        .await;
+    context
+        .warp_to_slot(request.slot + REQUEST_EXPIRY_SLOTS + 1)
+        .unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .cancel_request(
+                &mut context,
+                &randomness_request.pubkey(),
+                &fake_requester,
+                &fake_requester.pubkey(),
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+
+    let request = test_randomness_oracle
+        .get_request(&mut context, &randomness_request.pubkey())
+        .await;
+    assert_eq!(request.requester, requester.pubkey());
+}
+
+#[tokio::test]
+async fn fail_request_queue_full() {
+    let (mut context, test_randomness_oracle, requester, _, _) = setup().await;
+
+    for _ in 0..MAX_QUEUE_REQUESTS {
+        request(&mut context, &test_randomness_oracle, &requester).await;
+    }
+
+    assert_eq!(
+        test_randomness_oracle
+            .request(
+                &mut context,
+                &Keypair::new(),
+                &requester,
+                None,
+                SEED,
+                None,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::RequestQueueFull)
+    );
+
+    // Fulfilled request makes room for another one
+    let request_queue = test_randomness_oracle.get_request_queue(&mut context).await;
+    context.warp_to_slot(3).unwrap();
+    test_randomness_oracle
+        .fulfill(&mut context, &request_queue.requests()[0])
+        .await
+        .unwrap();
+    request(&mut context, &test_randomness_oracle, &requester).await;
+}
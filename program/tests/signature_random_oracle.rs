@@ -0,0 +1,185 @@
+mod utils;
+
+use randomness_oracle_program::{
+    ed25519, error::RandomnessOracleError, id, instruction, state::UpdateMode,
+};
+use solana_program::{
+    hash::{hash, hashv},
+    slot_hashes::SlotHashes,
+};
+use solana_program_test::*;
+use solana_sdk::{signature::Keypair, signer::Signer, transaction::Transaction};
+use std::convert::TryInto;
+use utils::*;
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::with_mode(UpdateMode::Signature);
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    (context, test_randomness_oracle)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let signature = test_randomness_oracle.sign(&mut context).await;
+    test_randomness_oracle
+        .commit(&mut context, hash(&signature).to_bytes())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+
+    test_randomness_oracle
+        .update_with_signature(&mut context, signature)
+        .await
+        .unwrap();
+
+    // Signature is mixed with the hash of the commitment slot
+    let slot_hashes = context
+        .banks_client
+        .get_sysvar::<SlotHashes>()
+        .await
+        .unwrap();
+    let slot_hash = slot_hashes.get(&3).unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert!(!randomness_oracle.has_commitment());
+    assert_eq!(randomness_oracle.slot, 5);
+    assert_eq!(randomness_oracle.slot_hash, slot_hash.to_bytes());
+    assert_eq!(
+        randomness_oracle.value,
+        hashv(&[&signature, slot_hash.as_ref()]).to_bytes()
+    );
+}
+
+#[tokio::test]
+async fn fail_without_commitment() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let signature = test_randomness_oracle.sign(&mut context).await;
+
+    assert_eq!(
+        test_randomness_oracle
+            .update_with_signature(&mut context, signature)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::NoPendingCommitment)
+    );
+}
+
+#[tokio::test]
+async fn fail_signature_mismatch() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&[1u8; 64]).to_bytes())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+
+    let signature = test_randomness_oracle.sign(&mut context).await;
+
+    assert_eq!(
+        test_randomness_oracle
+            .update_with_signature(&mut context, signature)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::CommitmentMismatch)
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_signer() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    let message = randomness_oracle.signature_message(&test_randomness_oracle.keypair.pubkey());
+
+    // Signature is valid but made by some other key
+    let fake_authority = Keypair::new();
+    let signature: [u8; 64] = fake_authority
+        .sign_message(&message)
+        .as_ref()
+        .try_into()
+        .unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&signature).to_bytes())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[
+            ed25519::new_ed25519_instruction(&fake_authority.pubkey(), &signature, &message, 0),
+            instruction::update_randomness_oracle_with_signature(
+                &id(),
+                &test_randomness_oracle.keypair.pubkey(),
+                &context.payer.pubkey(),
+                None,
+                signature,
+            ),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::InvalidSignatureVerification)
+    );
+}
+
+#[tokio::test]
+async fn fail_without_ed25519_instruction() {
+    let (mut context, test_randomness_oracle) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&[1u8; 64]).to_bytes())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(5).unwrap();
+
+    let tx = Transaction::new_signed_with_payer(
+        &[instruction::update_randomness_oracle_with_signature(
+            &id(),
+            &test_randomness_oracle.keypair.pubkey(),
+            &context.payer.pubkey(),
+            None,
+            [1u8; 64],
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidSignatureVerification)
+    );
+}
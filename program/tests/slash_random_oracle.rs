@@ -0,0 +1,209 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    find_bond_vault_address, id,
+    state::{
+        RandomnessOracle, UpdateMode, MAX_REVEAL_DELAY_SLOTS, MIN_BOND_LAMPORTS, SLASH_DIVISOR,
+    },
+};
+use solana_program::hash::hash;
+use solana_program_test::*;
+use solana_sdk::{
+    program_pack::Pack, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use utils::*;
+
+const SEED: [u8; 32] = [7u8; 32];
+const BOND: u64 = 1_000_000_000;
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle, Keypair) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::with_mode(UpdateMode::CommitReveal);
+    test_randomness_oracle
+        .init_with_bond(&mut context, BOND)
+        .await
+        .unwrap();
+
+    let caller = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &caller.pubkey(),
+            BOND,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (context, test_randomness_oracle, caller)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle, caller) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&SEED).to_bytes())
+        .await
+        .unwrap();
+
+    context
+        .warp_to_slot(3 + MAX_REVEAL_DELAY_SLOTS + 1)
+        .unwrap();
+
+    test_randomness_oracle
+        .slash(&mut context, &caller)
+        .await
+        .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert!(!randomness_oracle.has_commitment());
+
+    let (bond_vault, _) = find_bond_vault_address(&id(), &test_randomness_oracle.keypair.pubkey());
+    let bond_vault_account = get_account(&mut context, &bond_vault).await;
+    assert_eq!(bond_vault_account.lamports, BOND - BOND / SLASH_DIVISOR);
+
+    let caller_account = get_account(&mut context, &caller.pubkey()).await;
+    assert_eq!(caller_account.lamports, BOND + BOND / SLASH_DIVISOR);
+}
+
+#[tokio::test]
+async fn success_min_bond() {
+    let (mut context, _, caller) = setup().await;
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    let test_randomness_oracle = TestRandomnessOracle::with_mode(UpdateMode::CommitReveal);
+    test_randomness_oracle
+        .init_with_bond(&mut context, MIN_BOND_LAMPORTS)
+        .await
+        .unwrap();
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&SEED).to_bytes())
+        .await
+        .unwrap();
+
+    context
+        .warp_to_slot(3 + MAX_REVEAL_DELAY_SLOTS + 1)
+        .unwrap();
+
+    test_randomness_oracle
+        .slash(&mut context, &caller)
+        .await
+        .unwrap();
+
+    // Slashed minimum bond keeps the vault rent exempt
+    let (bond_vault, _) = find_bond_vault_address(&id(), &test_randomness_oracle.keypair.pubkey());
+    let bond_vault_account = get_account(&mut context, &bond_vault).await;
+    assert_eq!(
+        bond_vault_account.lamports,
+        MIN_BOND_LAMPORTS - MIN_BOND_LAMPORTS / SLASH_DIVISOR
+    );
+    assert!(rent.is_exempt(bond_vault_account.lamports, 0));
+
+    let caller_account = get_account(&mut context, &caller.pubkey()).await;
+    assert_eq!(
+        caller_account.lamports,
+        BOND + MIN_BOND_LAMPORTS / SLASH_DIVISOR
+    );
+}
+
+#[tokio::test]
+async fn fail_bond_below_minimum() {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::with_mode(UpdateMode::CommitReveal);
+    assert_eq!(
+        test_randomness_oracle
+            .init_with_bond(&mut context, MIN_BOND_LAMPORTS - 1)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::InsufficientBond)
+    );
+}
+
+#[tokio::test]
+async fn success_bond_returned_on_close() {
+    let (mut context, test_randomness_oracle, _) = setup().await;
+    let destination = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+
+    test_randomness_oracle
+        .close(&mut context, &destination.pubkey())
+        .await
+        .unwrap();
+
+    let destination_account = get_account(&mut context, &destination.pubkey()).await;
+    assert_eq!(
+        destination_account.lamports,
+        BOND + rent.minimum_balance(RandomnessOracle::LEN)
+    );
+}
+
+#[tokio::test]
+async fn fail_reveal_not_expired() {
+    let (mut context, test_randomness_oracle, caller) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&SEED).to_bytes())
+        .await
+        .unwrap();
+
+    context.warp_to_slot(3 + MAX_REVEAL_DELAY_SLOTS).unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .slash(&mut context, &caller)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::RevealNotExpired)
+    );
+}
+
+#[tokio::test]
+async fn fail_no_commitment() {
+    let (mut context, test_randomness_oracle, caller) = setup().await;
+    context
+        .warp_to_slot(3 + MAX_REVEAL_DELAY_SLOTS + 1)
+        .unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .slash(&mut context, &caller)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::RevealNotExpired)
+    );
+}
+
+#[tokio::test]
+async fn fail_close_with_pending_commitment() {
+    let (mut context, test_randomness_oracle, _) = setup().await;
+    let destination = Keypair::new();
+    context.warp_to_slot(3).unwrap();
+
+    test_randomness_oracle
+        .commit(&mut context, hash(&SEED).to_bytes())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .close(&mut context, &destination.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::CommitmentAlreadyPending)
+    );
+}
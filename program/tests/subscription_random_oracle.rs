@@ -0,0 +1,321 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError, find_treasury_address, id, state::REQUEST_EXPIRY_SLOTS,
+};
+use solana_program_test::*;
+use solana_sdk::{
+    pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
+    transaction::Transaction,
+};
+use utils::*;
+
+const SEED: [u8; 32] = [1u8; 32];
+
+async fn setup() -> (ProgramTestContext, TestRandomnessOracle, Keypair, u64) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    // Fee has to keep the treasury rent exempt
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let request_fee = rent.minimum_balance(0);
+    test_randomness_oracle
+        .set_request_fee(&mut context, request_fee)
+        .await
+        .unwrap();
+
+    // Owner pays for the subscription account only
+    let owner = Keypair::new();
+    let tx = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &owner.pubkey(),
+            1_000_000_000,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+    context.banks_client.process_transaction(tx).await.unwrap();
+
+    (context, test_randomness_oracle, owner, request_fee)
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle, owner, request_fee) = setup().await;
+    let (treasury, _) = find_treasury_address(&id(), &test_randomness_oracle.keypair.pubkey());
+
+    let subscription = test_randomness_oracle
+        .create_subscription(&mut context, &owner)
+        .await
+        .unwrap();
+    test_randomness_oracle
+        .fund_subscription(&mut context, &subscription, request_fee * 3)
+        .await
+        .unwrap();
+
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &randomness_request,
+            &owner,
+            Some(&subscription),
+            SEED,
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    // Fee is reserved, but not paid until fulfillment
+    assert_eq!(context.banks_client.get_balance(treasury).await.unwrap(), 0);
+    let subscription_data = test_randomness_oracle
+        .get_subscription(&mut context, &subscription)
+        .await;
+    assert_eq!(subscription_data.balance, request_fee * 2);
+    assert_eq!(subscription_data.pending_requests, 1);
+
+    context.warp_to_slot(3).unwrap();
+    test_randomness_oracle
+        .fulfill(&mut context, &randomness_request.pubkey())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        context.banks_client.get_balance(treasury).await.unwrap(),
+        request_fee
+    );
+    let subscription_data = test_randomness_oracle
+        .get_subscription(&mut context, &subscription)
+        .await;
+    assert_eq!(subscription_data.balance, request_fee * 2);
+    assert_eq!(subscription_data.pending_requests, 0);
+    assert_eq!(subscription_data.request_count, 1);
+}
+
+#[tokio::test]
+async fn success_cancel_expired_request() {
+    let (mut context, test_randomness_oracle, owner, request_fee) = setup().await;
+
+    let subscription = test_randomness_oracle
+        .create_subscription(&mut context, &owner)
+        .await
+        .unwrap();
+    test_randomness_oracle
+        .fund_subscription(&mut context, &subscription, request_fee)
+        .await
+        .unwrap();
+
+    let randomness_request = Keypair::new();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &randomness_request,
+            &owner,
+            Some(&subscription),
+            SEED,
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+    let request = test_randomness_oracle
+        .get_request(&mut context, &randomness_request.pubkey())
+        .await;
+
+    context
+        .warp_to_slot(request.slot + REQUEST_EXPIRY_SLOTS + 1)
+        .unwrap();
+    test_randomness_oracle
+        .cancel_request(
+            &mut context,
+            &randomness_request.pubkey(),
+            &owner,
+            &owner.pubkey(),
+        )
+        .await
+        .unwrap();
+
+    // Reserved fee is returned to the balance
+    let subscription_data = test_randomness_oracle
+        .get_subscription(&mut context, &subscription)
+        .await;
+    assert_eq!(subscription_data.balance, request_fee);
+    assert_eq!(subscription_data.pending_requests, 0);
+    assert_eq!(subscription_data.request_count, 0);
+
+    test_randomness_oracle
+        .cancel_subscription(&mut context, &subscription, &owner, &owner.pubkey())
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn success_cancel() {
+    let (mut context, test_randomness_oracle, owner, request_fee) = setup().await;
+
+    let subscription = test_randomness_oracle
+        .create_subscription(&mut context, &owner)
+        .await
+        .unwrap();
+    test_randomness_oracle
+        .fund_subscription(&mut context, &subscription, request_fee)
+        .await
+        .unwrap();
+    let lamports = context
+        .banks_client
+        .get_balance(subscription)
+        .await
+        .unwrap();
+
+    let destination = Pubkey::new_unique();
+    test_randomness_oracle
+        .cancel_subscription(&mut context, &subscription, &owner, &destination)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        context.banks_client.get_balance(destination).await.unwrap(),
+        lamports
+    );
+    assert_eq!(
+        context
+            .banks_client
+            .get_balance(subscription)
+            .await
+            .unwrap(),
+        0
+    );
+}
+
+#[tokio::test]
+async fn fail_insufficient_balance() {
+    let (mut context, test_randomness_oracle, owner, request_fee) = setup().await;
+
+    let subscription = test_randomness_oracle
+        .create_subscription(&mut context, &owner)
+        .await
+        .unwrap();
+    test_randomness_oracle
+        .fund_subscription(&mut context, &subscription, request_fee - 1)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .request(
+                &mut context,
+                &Keypair::new(),
+                &owner,
+                Some(&subscription),
+                SEED,
+                None,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::InsufficientSubscriptionBalance)
+    );
+}
+
+#[tokio::test]
+async fn fail_other_owner() {
+    let (mut context, test_randomness_oracle, owner, request_fee) = setup().await;
+
+    let subscription = test_randomness_oracle
+        .create_subscription(&mut context, &owner)
+        .await
+        .unwrap();
+    test_randomness_oracle
+        .fund_subscription(&mut context, &subscription, request_fee)
+        .await
+        .unwrap();
+
+    // Requester can't spend someone else's subscription
+    assert_eq!(
+        test_randomness_oracle
+            .request(
+                &mut context,
+                &Keypair::new(),
+                &Keypair::new(),
+                Some(&subscription),
+                SEED,
+                None,
+                vec![],
+                vec![],
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(1, RandomnessOracleError::InvalidSubscription)
+    );
+}
+
+#[tokio::test]
+async fn fail_cancel_wrong_owner() {
+    let (mut context, test_randomness_oracle, owner, _) = setup().await;
+
+    let subscription = test_randomness_oracle
+        .create_subscription(&mut context, &owner)
+        .await
+        .unwrap();
+
+    let fake_owner = Keypair::new();
+    assert_eq!(
+        test_randomness_oracle
+            .cancel_subscription(
+                &mut context,
+                &subscription,
+                &fake_owner,
+                &fake_owner.pubkey()
+            )
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+}
+
+#[tokio::test]
+async fn fail_cancel_with_pending_request() {
+    let (mut context, test_randomness_oracle, owner, request_fee) = setup().await;
+
+    let subscription = test_randomness_oracle
+        .create_subscription(&mut context, &owner)
+        .await
+        .unwrap();
+    test_randomness_oracle
+        .fund_subscription(&mut context, &subscription, request_fee)
+        .await
+        .unwrap();
+    test_randomness_oracle
+        .request(
+            &mut context,
+            &Keypair::new(),
+            &owner,
+            Some(&subscription),
+            SEED,
+            None,
+            vec![],
+            vec![],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(
+        test_randomness_oracle
+            .cancel_subscription(&mut context, &subscription, &owner, &owner.pubkey())
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::SubscriptionHasPendingRequests)
+    );
+}
@@ -27,4 +27,6 @@ async fn success() {
     assert_eq!(randomness_oracle.authority, context.payer.pubkey());
     assert_eq!(randomness_oracle.slot, 3);
     assert_eq!(randomness_oracle.value, [1u8; 32]);
+    assert_ne!(randomness_oracle.unix_timestamp, 0);
+    assert_eq!(randomness_oracle.round, 1);
 }
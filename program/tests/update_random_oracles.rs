@@ -0,0 +1,298 @@
+mod utils;
+
+use randomness_oracle_program::{
+    error::RandomnessOracleError,
+    id,
+    instruction::{
+        self, RandomnessOracleInstruction, RandomnessOracleUpdate, MAX_BATCH_UPDATES,
+        MAX_TRANSACTION_SIZE,
+    },
+};
+use solana_program_test::*;
+use solana_sdk::{
+    instruction::{AccountMeta, Instruction},
+    message::Message,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    sysvar,
+    transaction::Transaction,
+    transport,
+};
+use utils::*;
+
+async fn setup() -> (
+    ProgramTestContext,
+    TestRandomnessOracle,
+    TestRandomnessOracle,
+    Keypair,
+) {
+    let mut context = program_test().start_with_context().await;
+
+    let test_randomness_oracle = TestRandomnessOracle::new();
+    test_randomness_oracle.init(&mut context).await.unwrap();
+
+    let test_randomness_oracle_with_history = TestRandomnessOracle::new();
+    test_randomness_oracle_with_history
+        .init(&mut context)
+        .await
+        .unwrap();
+    let history = Keypair::new();
+    test_randomness_oracle_with_history
+        .init_history(&mut context, &history)
+        .await
+        .unwrap();
+
+    (
+        context,
+        test_randomness_oracle,
+        test_randomness_oracle_with_history,
+        history,
+    )
+}
+
+async fn update_all(
+    context: &mut ProgramTestContext,
+    authority: &Keypair,
+    updates: &[RandomnessOracleUpdate],
+) -> transport::Result<()> {
+    for instruction in instruction::update_randomness_oracles(&id(), &authority.pubkey(), updates) {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+        context.banks_client.process_transaction(tx).await?;
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn success() {
+    let (mut context, test_randomness_oracle, test_randomness_oracle_with_history, history) =
+        setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    update_all(
+        &mut context,
+        &authority,
+        &[
+            RandomnessOracleUpdate {
+                randomness_oracle: test_randomness_oracle.keypair.pubkey(),
+                history: None,
+                value: [1u8; 32],
+            },
+            RandomnessOracleUpdate {
+                randomness_oracle: test_randomness_oracle_with_history.keypair.pubkey(),
+                history: Some(history.pubkey()),
+                value: [2u8; 32],
+            },
+        ],
+    )
+    .await
+    .unwrap();
+
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.slot, 3);
+    assert_eq!(randomness_oracle.value, [1u8; 32]);
+    assert_eq!(randomness_oracle.round, 1);
+
+    let randomness_oracle = test_randomness_oracle_with_history
+        .get_data(&mut context)
+        .await;
+    assert_eq!(randomness_oracle.slot, 3);
+    assert_eq!(randomness_oracle.value, [2u8; 32]);
+    assert_eq!(randomness_oracle.round, 1);
+
+    let randomness_oracle_history = test_randomness_oracle_with_history
+        .get_history(&mut context, &history.pubkey())
+        .await;
+    let last = randomness_oracle_history.iter().last().unwrap();
+    assert_eq!((last.slot, last.round, last.value), (3, 1, [2u8; 32]));
+}
+
+#[test]
+fn success_split_into_transactions() {
+    let authority = Pubkey::new_unique();
+    let updates: Vec<_> = (0..50)
+        .map(|i| RandomnessOracleUpdate {
+            randomness_oracle: Pubkey::new_unique(),
+            history: if i % 2 == 0 {
+                Some(Pubkey::new_unique())
+            } else {
+                None
+            },
+            value: [i as u8; 32],
+        })
+        .collect();
+
+    let instructions = instruction::update_randomness_oracles(&id(), &authority, &updates);
+    assert!(instructions.len() > 1);
+
+    let mut packed = 0;
+    for instruction in instructions {
+        let message = Message::new(&[instruction.clone()], Some(&authority));
+        assert!(1 + 64 + message.serialize().len() <= MAX_TRANSACTION_SIZE);
+        assert!(
+            instruction
+                .accounts
+                .iter()
+                .filter(|a| a.is_writable)
+                .count()
+                <= 2 * MAX_BATCH_UPDATES
+        );
+
+        // Authority and clock, followed by randomness oracles and their histories
+        for account in &instruction.accounts[2..] {
+            if account.pubkey == updates[packed].randomness_oracle {
+                packed += 1;
+            } else {
+                assert_eq!(Some(account.pubkey), updates[packed - 1].history);
+            }
+        }
+    }
+    assert_eq!(packed, updates.len());
+}
+
+#[tokio::test]
+async fn success_max_batch_with_history() {
+    let mut context = program_test().start_with_context().await;
+
+    let mut updates = vec![];
+    let mut test_randomness_oracles = vec![];
+    for i in 0..MAX_BATCH_UPDATES {
+        let test_randomness_oracle = TestRandomnessOracle::new();
+        test_randomness_oracle.init(&mut context).await.unwrap();
+        let history = Keypair::new();
+        test_randomness_oracle
+            .init_history(&mut context, &history)
+            .await
+            .unwrap();
+
+        updates.push(RandomnessOracleUpdate {
+            randomness_oracle: test_randomness_oracle.keypair.pubkey(),
+            history: Some(history.pubkey()),
+            value: [i as u8; 32],
+        });
+        test_randomness_oracles.push(test_randomness_oracle);
+    }
+    context.warp_to_slot(3).unwrap();
+
+    let authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    assert_eq!(
+        instruction::update_randomness_oracles(&id(), &authority.pubkey(), &updates).len(),
+        1
+    );
+    update_all(&mut context, &authority, &updates)
+        .await
+        .unwrap();
+
+    for (i, test_randomness_oracle) in test_randomness_oracles.iter().enumerate() {
+        let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+        assert_eq!(randomness_oracle.value, [i as u8; 32]);
+        assert_eq!(randomness_oracle.round, 1);
+    }
+}
+
+#[tokio::test]
+async fn fail_too_many_updates() {
+    let (mut context, test_randomness_oracle, _, _) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let mut accounts = vec![
+        AccountMeta::new_readonly(context.payer.pubkey(), true),
+        AccountMeta::new_readonly(sysvar::clock::id(), false),
+    ];
+    accounts.extend(
+        (0..=MAX_BATCH_UPDATES)
+            .map(|_| AccountMeta::new(test_randomness_oracle.keypair.pubkey(), false)),
+    );
+
+    let tx = Transaction::new_signed_with_payer(
+        &[Instruction::new_with_borsh(
+            id(),
+            &RandomnessOracleInstruction::UpdateRandomnessOracles {
+                values: vec![[1u8; 32]; MAX_BATCH_UPDATES + 1],
+            },
+            accounts,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        context.last_blockhash,
+    );
+
+    assert_eq!(
+        context
+            .banks_client
+            .process_transaction(tx)
+            .await
+            .unwrap_err()
+            .unwrap(),
+        custom_error(0, RandomnessOracleError::TooManyUpdates)
+    );
+}
+
+#[tokio::test]
+async fn fail_wrong_authority() {
+    let (mut context, test_randomness_oracle, _, _) = setup().await;
+    context.warp_to_slot(3).unwrap();
+
+    let fake_authority = Keypair::new();
+    assert_eq!(
+        update_all(
+            &mut context,
+            &fake_authority,
+            &[RandomnessOracleUpdate {
+                randomness_oracle: test_randomness_oracle.keypair.pubkey(),
+                history: None,
+                value: [1u8; 32],
+            }],
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        custom_error(0, RandomnessOracleError::InvalidAuthority)
+    );
+}
+
+#[tokio::test]
+async fn fail_one_paused() {
+    let (mut context, test_randomness_oracle, test_randomness_oracle_with_history, history) =
+        setup().await;
+    test_randomness_oracle_with_history
+        .pause(&mut context)
+        .await
+        .unwrap();
+    context.warp_to_slot(3).unwrap();
+
+    let authority = Keypair::from_bytes(&context.payer.to_bytes()).unwrap();
+    assert_eq!(
+        update_all(
+            &mut context,
+            &authority,
+            &[
+                RandomnessOracleUpdate {
+                    randomness_oracle: test_randomness_oracle.keypair.pubkey(),
+                    history: None,
+                    value: [1u8; 32],
+                },
+                RandomnessOracleUpdate {
+                    randomness_oracle: test_randomness_oracle_with_history.keypair.pubkey(),
+                    history: Some(history.pubkey()),
+                    value: [2u8; 32],
+                },
+            ],
+        )
+        .await
+        .unwrap_err()
+        .unwrap(),
+        custom_error(0, RandomnessOracleError::OraclePaused)
+    );
+
+    // Whole batch is rejected
+    let randomness_oracle = test_randomness_oracle.get_data(&mut context).await;
+    assert_eq!(randomness_oracle.round, 0);
+}
@@ -2,10 +2,10 @@
 
 mod test_randomness_oracle;
 
-use randomness_oracle_program::processor;
-use solana_program::pubkey::Pubkey;
+use randomness_oracle_program::{error::RandomnessOracleError, processor};
+use solana_program::{instruction::InstructionError, pubkey::Pubkey};
 use solana_program_test::*;
-use solana_sdk::account::Account;
+use solana_sdk::{account::Account, transaction::TransactionError};
 pub use test_randomness_oracle::*;
 
 pub fn program_test() -> ProgramTest {
@@ -24,3 +24,13 @@ pub async fn get_account(context: &mut ProgramTestContext, pubkey: &Pubkey) -> A
         .expect("account not found")
         .expect("account empty")
 }
+
+/// Transaction error returned by the program for the instruction at `index`.
+pub fn custom_error(index: u8, error: RandomnessOracleError) -> TransactionError {
+    TransactionError::InstructionError(index, InstructionError::Custom(error as u32))
+}
+
+/// Transaction error returned by the runtime or another program for the instruction at `index`.
+pub fn instruction_error(index: u8, error: InstructionError) -> TransactionError {
+    TransactionError::InstructionError(index, error)
+}
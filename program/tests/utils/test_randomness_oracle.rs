@@ -1,38 +1,80 @@
-use randomness_oracle_program::{id, instruction, state::RandomnessOracle};
+use randomness_oracle_program::{
+    ed25519, find_request_queue_address, find_subscription_address, id, instruction,
+    state::{
+        CallbackAccount, RandomnessOracle, RandomnessOracleHistory, RandomnessOracleMetadata,
+        RandomnessRequest, RandomnessRequestQueue, Subscription, UpdateMode, MIN_BOND_LAMPORTS,
+    },
+};
 use solana_program_test::*;
 use solana_sdk::{
-    program_pack::Pack, signature::Keypair, signer::Signer, system_instruction,
+    program_pack::Pack, pubkey::Pubkey, signature::Keypair, signer::Signer, system_instruction,
     transaction::Transaction, transport,
 };
+use std::convert::TryInto;
 
 use super::get_account;
 
 pub struct TestRandomnessOracle {
     pub keypair: Keypair,
+    pub update_mode: UpdateMode,
 }
 
 impl TestRandomnessOracle {
     pub fn new() -> Self {
+        Self::with_mode(UpdateMode::Direct)
+    }
+
+    pub fn with_mode(update_mode: UpdateMode) -> Self {
         TestRandomnessOracle {
             keypair: Keypair::new(),
+            update_mode,
         }
     }
 
     pub async fn init(&self, context: &mut ProgramTestContext) -> transport::Result<()> {
+        self.init_with_bond(context, MIN_BOND_LAMPORTS).await
+    }
+
+    pub async fn init_with_bond(
+        &self,
+        context: &mut ProgramTestContext,
+        bond: u64,
+    ) -> transport::Result<()> {
         let rent = context.banks_client.get_rent().await.unwrap();
+
+        self.init_with_account(
+            context,
+            rent.minimum_balance(RandomnessOracle::LEN),
+            RandomnessOracle::LEN,
+            bond,
+        )
+        .await
+    }
+
+    pub async fn init_with_account(
+        &self,
+        context: &mut ProgramTestContext,
+        lamports: u64,
+        space: usize,
+        bond: u64,
+    ) -> transport::Result<()> {
         let tx = Transaction::new_signed_with_payer(
             &[
                 system_instruction::create_account(
                     &context.payer.pubkey(),
                     &self.keypair.pubkey(),
-                    rent.minimum_balance(RandomnessOracle::LEN),
-                    RandomnessOracle::LEN as u64,
+                    lamports,
+                    space as u64,
                     &id(),
                 ),
                 instruction::init_randomness_oracle(
                     &id(),
                     &self.keypair.pubkey(),
                     &context.payer.pubkey(),
+                    0,
+                    RandomnessOracleMetadata::default(),
+                    bond,
+                    self.update_mode,
                 ),
             ],
             Some(&context.payer.pubkey()),
@@ -43,16 +85,65 @@ impl TestRandomnessOracle {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn reinit(
+        &self,
+        context: &mut ProgramTestContext,
+        authority: &Keypair,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::init_randomness_oracle(
+                &id(),
+                &self.keypair.pubkey(),
+                &authority.pubkey(),
+                0,
+                RandomnessOracleMetadata::default(),
+                0,
+                self.update_mode,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
     pub async fn update(
         &self,
         context: &mut ProgramTestContext,
         value: [u8; 32],
     ) -> transport::Result<()> {
+        let history = self.get_data(context).await.history_address();
+
         let tx = Transaction::new_signed_with_payer(
             &[instruction::update_randomness_oracle(
                 &id(),
                 &self.keypair.pubkey(),
                 &context.payer.pubkey(),
+                history.as_ref(),
+                value,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn mix(
+        &self,
+        context: &mut ProgramTestContext,
+        value: [u8; 32],
+    ) -> transport::Result<()> {
+        let history = self.get_data(context).await.history_address();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::mix_randomness_oracle(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                history.as_ref(),
                 value,
             )],
             Some(&context.payer.pubkey()),
@@ -63,6 +154,509 @@ impl TestRandomnessOracle {
         context.banks_client.process_transaction(tx).await
     }
 
+    pub async fn sign(&self, context: &mut ProgramTestContext) -> [u8; 64] {
+        let randomness_oracle = self.get_data(context).await;
+        let message = randomness_oracle.signature_message(&self.keypair.pubkey());
+
+        context
+            .payer
+            .sign_message(&message)
+            .as_ref()
+            .try_into()
+            .unwrap()
+    }
+
+    pub async fn update_with_signature(
+        &self,
+        context: &mut ProgramTestContext,
+        signature: [u8; 64],
+    ) -> transport::Result<()> {
+        let randomness_oracle = self.get_data(context).await;
+        let message = randomness_oracle.signature_message(&self.keypair.pubkey());
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                ed25519::new_ed25519_instruction(&context.payer.pubkey(), &signature, &message, 0),
+                instruction::update_randomness_oracle_with_signature(
+                    &id(),
+                    &self.keypair.pubkey(),
+                    &context.payer.pubkey(),
+                    randomness_oracle.history_address().as_ref(),
+                    signature,
+                ),
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn commit(
+        &self,
+        context: &mut ProgramTestContext,
+        hash: [u8; 32],
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::commit_randomness(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                hash,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn reveal(
+        &self,
+        context: &mut ProgramTestContext,
+        seed: [u8; 32],
+    ) -> transport::Result<()> {
+        let history = self.get_data(context).await.history_address();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::reveal_randomness(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                history.as_ref(),
+                seed,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn slash(
+        &self,
+        context: &mut ProgramTestContext,
+        caller: &Keypair,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::slash_operator(
+                &id(),
+                &self.keypair.pubkey(),
+                &caller.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, caller],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn propose_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        new_authority: &Pubkey,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::propose_authority(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                new_authority,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn accept_authority(
+        &self,
+        context: &mut ProgramTestContext,
+        new_authority: &Keypair,
+    ) -> transport::Result<()> {
+        let authority = self.get_data(context).await.authority;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::accept_authority(
+                &id(),
+                &self.keypair.pubkey(),
+                &authority,
+                &new_authority.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, new_authority],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn close(
+        &self,
+        context: &mut ProgramTestContext,
+        destination: &Pubkey,
+    ) -> transport::Result<()> {
+        let history = self.get_data(context).await.history_address();
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::close_randomness_oracle(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                destination,
+                history.as_ref(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_min_update_interval(
+        &self,
+        context: &mut ProgramTestContext,
+        min_update_interval_slots: u64,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_min_update_interval(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                min_update_interval_slots,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn update_metadata(
+        &self,
+        context: &mut ProgramTestContext,
+        metadata: RandomnessOracleMetadata,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::update_metadata(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                metadata,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn pause(&self, context: &mut ProgramTestContext) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::pause_randomness_oracle(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn resume(&self, context: &mut ProgramTestContext) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::resume_randomness_oracle(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn set_request_fee(
+        &self,
+        context: &mut ProgramTestContext,
+        request_fee: u64,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::set_request_fee(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                request_fee,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn withdraw_fees(
+        &self,
+        context: &mut ProgramTestContext,
+        destination: &Pubkey,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::withdraw_fees(
+                &id(),
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                destination,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn create_subscription(
+        &self,
+        context: &mut ProgramTestContext,
+        owner: &Keypair,
+    ) -> transport::Result<Pubkey> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::create_subscription(
+                &id(),
+                &self.keypair.pubkey(),
+                &owner.pubkey(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await?;
+
+        let (subscription, _) =
+            find_subscription_address(&id(), &self.keypair.pubkey(), &owner.pubkey());
+
+        Ok(subscription)
+    }
+
+    pub async fn fund_subscription(
+        &self,
+        context: &mut ProgramTestContext,
+        subscription: &Pubkey,
+        amount: u64,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::fund_subscription(
+                &id(),
+                subscription,
+                &context.payer.pubkey(),
+                amount,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn cancel_subscription(
+        &self,
+        context: &mut ProgramTestContext,
+        subscription: &Pubkey,
+        owner: &Keypair,
+        destination: &Pubkey,
+    ) -> transport::Result<()> {
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::cancel_subscription(
+                &id(),
+                subscription,
+                &owner.pubkey(),
+                destination,
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, owner],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn get_subscription(
+        &self,
+        context: &mut ProgramTestContext,
+        subscription: &Pubkey,
+    ) -> Subscription {
+        let account = get_account(context, subscription).await;
+        Subscription::unpack_unchecked(&account.data).unwrap()
+    }
+
+    pub async fn init_history(
+        &self,
+        context: &mut ProgramTestContext,
+        history: &Keypair,
+    ) -> transport::Result<()> {
+        let rent = context.banks_client.get_rent().await.unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                system_instruction::create_account(
+                    &context.payer.pubkey(),
+                    &history.pubkey(),
+                    rent.minimum_balance(RandomnessOracleHistory::LEN),
+                    RandomnessOracleHistory::LEN as u64,
+                    &id(),
+                ),
+                instruction::init_randomness_oracle_history(
+                    &id(),
+                    &history.pubkey(),
+                    &self.keypair.pubkey(),
+                    &context.payer.pubkey(),
+                ),
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, history],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn get_history(
+        &self,
+        context: &mut ProgramTestContext,
+        history: &Pubkey,
+    ) -> RandomnessOracleHistory {
+        let account = get_account(context, history).await;
+        RandomnessOracleHistory::unpack_unchecked(&account.data).unwrap()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn request(
+        &self,
+        context: &mut ProgramTestContext,
+        randomness_request: &Keypair,
+        requester: &Keypair,
+        subscription: Option<&Pubkey>,
+        seed: [u8; 32],
+        callback_program: Option<Pubkey>,
+        callback_accounts: Vec<CallbackAccount>,
+        callback_data: Vec<u8>,
+    ) -> transport::Result<()> {
+        let rent = context.banks_client.get_rent().await.unwrap();
+
+        let tx = Transaction::new_signed_with_payer(
+            &[
+                system_instruction::create_account(
+                    &context.payer.pubkey(),
+                    &randomness_request.pubkey(),
+                    rent.minimum_balance(RandomnessRequest::LEN),
+                    RandomnessRequest::LEN as u64,
+                    &id(),
+                ),
+                instruction::request_randomness(
+                    &id(),
+                    &randomness_request.pubkey(),
+                    &self.keypair.pubkey(),
+                    &requester.pubkey(),
+                    subscription,
+                    seed,
+                    callback_program,
+                    callback_accounts,
+                    callback_data,
+                ),
+            ],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, randomness_request, requester],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn fulfill(
+        &self,
+        context: &mut ProgramTestContext,
+        randomness_request: &Pubkey,
+    ) -> transport::Result<()> {
+        let request = self.get_request(context, randomness_request).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::fulfill_randomness(
+                &id(),
+                randomness_request,
+                &self.keypair.pubkey(),
+                &context.payer.pubkey(),
+                request.subscription().as_ref(),
+                request.callback_program().as_ref(),
+                request.callback_accounts(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn cancel_request(
+        &self,
+        context: &mut ProgramTestContext,
+        randomness_request: &Pubkey,
+        caller: &Keypair,
+        destination: &Pubkey,
+    ) -> transport::Result<()> {
+        let request = self.get_request(context, randomness_request).await;
+
+        let tx = Transaction::new_signed_with_payer(
+            &[instruction::cancel_randomness_request(
+                &id(),
+                randomness_request,
+                &request.randomness_oracle,
+                &caller.pubkey(),
+                destination,
+                request.subscription().as_ref(),
+            )],
+            Some(&context.payer.pubkey()),
+            &[&context.payer, caller],
+            context.last_blockhash,
+        );
+
+        context.banks_client.process_transaction(tx).await
+    }
+
+    pub async fn get_request(
+        &self,
+        context: &mut ProgramTestContext,
+        randomness_request: &Pubkey,
+    ) -> RandomnessRequest {
+        let account = get_account(context, randomness_request).await;
+        RandomnessRequest::unpack_unchecked(&account.data).unwrap()
+    }
+
+    pub async fn get_request_queue(
+        &self,
+        context: &mut ProgramTestContext,
+    ) -> RandomnessRequestQueue {
+        let (request_queue, _) = find_request_queue_address(&id(), &self.keypair.pubkey());
+        let account = get_account(context, &request_queue).await;
+        RandomnessRequestQueue::unpack_unchecked(&account.data).unwrap()
+    }
+
     pub async fn get_data(&self, context: &mut ProgramTestContext) -> RandomnessOracle {
         let account = get_account(context, &self.keypair.pubkey()).await;
         RandomnessOracle::unpack_unchecked(&account.data).unwrap()